@@ -0,0 +1,238 @@
+# Deutsche Texte der Oberfläche
+
+nav-calendar = Kalender
+nav-agenda = Agenda
+nav-search = Suche
+nav-tasks = Aufgaben
+nav-settings = Einstellungen
+
+weekday-mon = Montag
+weekday-tue = Dienstag
+weekday-wed = Mittwoch
+weekday-thu = Donnerstag
+weekday-fri = Freitag
+weekday-sat = Samstag
+weekday-sun = Sonntag
+weekday-short-mon = Mo
+weekday-short-tue = Di
+weekday-short-wed = Mi
+weekday-short-thu = Do
+weekday-short-fri = Fr
+weekday-short-sat = Sa
+weekday-short-sun = So
+month-1 = Januar
+month-2 = Februar
+month-3 = März
+month-4 = April
+month-5 = Mai
+month-6 = Juni
+month-7 = Juli
+month-8 = August
+month-9 = September
+month-10 = Oktober
+month-11 = November
+month-12 = Dezember
+date-long = { $weekday }, { $date }
+week-title = KW { $week }, { $year }
+all-day = Ganztägig
+no-appointments = Keine Termine
+
+priority-high = Hoch
+priority-middle = Mittel
+priority-low = Niedrig
+frequency-daily = Täglich
+frequency-weekly = Wöchentlich
+frequency-monthly = Monatlich
+frequency-yearly = Jährlich
+
+button-add = Hinzufügen
+button-cancel = Abbrechen
+button-delete = Löschen
+button-dismiss = Verwerfen
+button-duplicate = Duplizieren
+button-edit = Bearbeiten
+button-move = Verschieben
+button-move-to-date = Auf Datum verschieben…
+button-open = Öffnen
+button-remove = Entfernen
+button-rename = Umbenennen
+button-submit = Speichern
+
+toolbar-ics-placeholder = pfad/zu/kalender.ics
+toolbar-import = .ics importieren
+toolbar-export = .ics exportieren
+toolbar-tags = Tags
+toolbar-calendars = Kalender
+toolbar-undo = Rückgängig
+toolbar-redo = Wiederholen
+
+dialog-date = Datum
+dialog-all-day = Ganztägig
+dialog-start = Beginn
+dialog-end = Ende
+dialog-end-placeholder = hh:mm oder +1h30m
+dialog-repeat = Wiederholen
+dialog-frequency = Häufigkeit
+dialog-every = Alle
+dialog-on-days = An Tagen
+dialog-by-day-placeholder = MO, WE oder 3MO
+dialog-count = Anzahl
+dialog-until = Bis
+dialog-reminders = Erinnerungen
+dialog-alert-placeholder = { $date } hh:mm oder -P1D, -PT15M
+dialog-add-reminder = Erinnerung hinzufügen
+dialog-tags = Tags
+dialog-description = Beschreibung
+dialog-priority = Priorität
+dialog-calendar = Kalender
+
+label-priority = Priorität: { $priority }
+label-tags = Tags: { $tags }
+label-reminder = Erinnerung: { $alert }
+label-repeats = Wiederholung: { $frequency }
+
+scope-question = Dieser Termin wiederholt sich. Änderungen übernehmen für
+scope-this = Diesen Termin
+scope-following = Diesen und alle folgenden Termine
+scope-all = Alle Termine
+scope-other-calendar = Nur ganze Serien können in einen anderen Kalender wechseln
+delete-question = „{ $description }“ löschen?
+delete-this = Diesen Termin löschen
+delete-following = Diesen und alle folgenden Termine löschen
+delete-all = Alle Termine löschen
+move-to = Verschieben auf
+
+quick-add-placeholder = Zahnarzt tomorrow 14:30 #gesundheit !high remind 1d before
+quick-add-repeats = Wiederholung { $frequency }, Intervall { $interval } { $days }
+quick-add-unclear = Unklar: { $problem }
+quick-add-complete = Im Dialog vervollständigen
+quick-add-open-dialog = Dialog öffnen
+quick-add-unknown-priority = unbekannte Priorität „{ $word }“
+quick-add-two-times = mehr als eine Uhrzeit angegeben, „{ $word }“
+quick-add-two-dates = mehr als ein Datum angegeben
+quick-add-two-priorities = mehr als eine Priorität angegeben
+quick-add-two-repetitions = mehr als eine Wiederholung angegeben
+quick-add-reminder-time = eine Erinnerung braucht eine Zeit wie „remind 1d before“
+quick-add-invalid-amount = „{ $word }“ ist keine verwendbare Zeitspanne
+quick-add-every = „every“ braucht day, week, month, year oder Wochentage
+quick-add-no-description = keine Beschreibung
+quick-add-after-midnight = der Termin endet nach Mitternacht
+quick-add-ends-before-start = der Termin endet vor seinem Beginn
+quick-add-length-needs-start = eine Dauer braucht eine Anfangszeit
+
+reminder-banner = Erinnerung: { $description } am { $date }, { $time }
+reminder-snooze = { $minutes } Min. schlummern
+
+tags-title = Tags
+tags-empty = Noch keine Tags
+tags-new-name = Neuer Name
+tags-merge-hint = Ein vorhandener Name führt beide Tags zusammen
+
+history-add = „{ $description }“ hinzufügen
+history-edit = „{ $description }“ bearbeiten
+history-delete = „{ $description }“ löschen
+history-duplicate = „{ $description }“ duplizieren
+history-move = „{ $description }“ auf { $date } verschieben
+history-copy = „{ $description }“ nach { $date } kopieren
+history-import = { $count } Termine und { $tasks } Aufgaben importieren
+history-rename-tag = Tag „{ $from }“ in „{ $to }“ umbenennen
+history-merge-tag = Tag „{ $from }“ mit „{ $to }“ zusammenführen
+history-add-task = Aufgabe „{ $description }“ hinzufügen
+history-edit-task = Aufgabe „{ $description }“ bearbeiten
+history-delete-task = Aufgabe „{ $description }“ löschen
+history-done-task = „{ $description }“ abhaken
+history-reopen-task = „{ $description }“ wieder öffnen
+history-checklist = Checkliste von „{ $description }“ ändern
+
+status-save-failed = Speichern fehlgeschlagen: { $error }
+status-deleted = „{ $description }“ gelöscht
+status-undid = Rückgängig: { $label }
+status-redid = Wiederholt: { $label }
+status-restored = { $path } wiederhergestellt
+status-imported = { $count } Termine und { $tasks } Aufgaben importiert
+status-imported-skipped = { $count } Termine und { $tasks } Aufgaben importiert, { $skipped } davon sind in schreibgeschützten Kalendern und wurden nicht geändert
+status-import-failed = Import fehlgeschlagen: { $error }
+status-exported = { $count } Termine und { $tasks } Aufgaben exportiert
+status-export-failed = Export fehlgeschlagen: { $error }
+
+load-error-title = Deine Termine konnten nicht geladen werden
+load-error-restore = { $path }{ $modified } wiederherstellen
+load-error-discard = Mit einem leeren Kalender beginnen
+error-restore = { $path } konnte nicht wiederhergestellt werden: { $error }
+error-file-access = Zugriff auf die Datei fehlgeschlagen: { $error }
+storage-damaged = Datei ist beschädigt: { $error }
+storage-newer-version = Dateiversion { $version } ist neuer als diese Version von Planer
+storage-migration = Datei konnte nicht aktualisiert werden: { $error }
+ical-missing-calendar = kein VCALENDAR gefunden
+ical-unterminated = { $name } ist nicht abgeschlossen
+ical-invalid-property = ungültige Eigenschaft: { $line }
+
+agenda-title = Agenda
+agenda-range = Nächste { $days } Tage
+agenda-show-past = Vergangene zeigen ({ $count })
+agenda-hide-past = Vergangene ausblenden ({ $count })
+agenda-empty = Nichts geplant
+agenda-today = Heute, { $date }
+agenda-tomorrow = Morgen, { $date }
+
+search-placeholder = Suche, z. B. zahnarzt tag:gesundheit priority:high after:1.10.2026 before:31.12.2026
+search-hint = Suchbegriff eingeben
+search-found = { $count } gefunden
+search-repeats = (wiederholt sich)
+query-missing-value = { $filter }: braucht einen Wert
+query-unknown-priority = unbekannte Priorität „{ $priority }“, verwende hoch, mittel oder niedrig
+query-invalid-date = „{ $date }“ ist kein Datum wie { $format }
+
+settings-title = Einstellungen
+settings-theme = Design
+settings-start-view = Starten mit
+settings-date-format = Datumsformat
+settings-language = Sprache
+settings-first-weekday = Wochen beginnen am
+settings-data-dir = Datenordner
+settings-data-dir-hint = Ort der saved.yml, leer für den Standard. Ein Ordner ohne saved.yml erhält die aktuellen Termine.
+settings-apply = Übernehmen
+settings-default = Standard
+settings-saved-in = Einstellungen werden in { $path } gespeichert
+settings-load-failed = Die Einstellungen konnten nicht gelesen werden, Änderungen werden erst gespeichert, wenn die Datei ersetzt wird: { $error }
+settings-overwrite = Einstellungsdatei ersetzen
+settings-save-failed = Die Einstellungen konnten nicht gespeichert werden: { $error }
+settings-themes-hint = Eigene Paletten werden aus { $path } gelesen
+settings-themes-failed = Die Paletten konnten nicht gelesen werden: { $error }
+settings-theme-missing = In { $path } gibt es keine Palette namens { $name }
+theme-light = Hell
+theme-dark = Dunkel
+theme-system = Wie das System
+view-year = Jahr
+view-month = Monat
+view-week = Woche
+view-day = Tag
+language-system = Wie das System
+week-start-language = Wie in der Sprache üblich
+
+tasks-title = Aufgaben
+tasks-new = Neue Aufgabe
+tasks-show-done = Erledigte zeigen ({ $count })
+tasks-hide-done = Erledigte ausblenden ({ $count })
+tasks-empty = Nichts zu tun
+task-group-overdue = Überfällig
+task-group-today = Heute
+task-group-upcoming = Demnächst
+task-group-someday = Irgendwann
+task-group-done = Erledigt
+task-due-on = fällig am { $date }
+task-done-on = erledigt am { $date }
+task-description = Aufgabe
+task-due = Fällig
+task-due-placeholder = { $format }, leer für irgendwann
+task-checklist = Checkliste
+task-add-item = Punkt hinzufügen
+
+calendars-title = Kalender
+calendars-main = Persönlich
+calendars-read-only = Schreibgeschützt
+calendars-read-only-hint = Dieser Kalender ist schreibgeschützt
+calendars-unreadable = Konnte nicht gelesen werden, Änderungen werden nicht gespeichert
+calendars-new-name = Neuer Kalender
+calendars-load-failed = { $name } konnte nicht gelesen werden: { $error }
+calendars-save-failed = Die Kalender konnten nicht gespeichert werden: { $error }
@@ -0,0 +1,238 @@
+# English texts of the user interface, the fallback for all other locales
+
+nav-calendar = Calendar
+nav-agenda = Agenda
+nav-search = Search
+nav-tasks = Tasks
+nav-settings = Settings
+
+weekday-mon = Monday
+weekday-tue = Tuesday
+weekday-wed = Wednesday
+weekday-thu = Thursday
+weekday-fri = Friday
+weekday-sat = Saturday
+weekday-sun = Sunday
+weekday-short-mon = Mo
+weekday-short-tue = Tu
+weekday-short-wed = We
+weekday-short-thu = Th
+weekday-short-fri = Fr
+weekday-short-sat = Sa
+weekday-short-sun = Su
+month-1 = January
+month-2 = February
+month-3 = March
+month-4 = April
+month-5 = May
+month-6 = June
+month-7 = July
+month-8 = August
+month-9 = September
+month-10 = October
+month-11 = November
+month-12 = December
+date-long = { $weekday }, { $date }
+week-title = Week { $week }, { $year }
+all-day = All day
+no-appointments = No appointments
+
+priority-high = High
+priority-middle = Middle
+priority-low = Low
+frequency-daily = Daily
+frequency-weekly = Weekly
+frequency-monthly = Monthly
+frequency-yearly = Yearly
+
+button-add = Add
+button-cancel = Cancel
+button-delete = Delete
+button-dismiss = Dismiss
+button-duplicate = Duplicate
+button-edit = Edit
+button-move = Move
+button-move-to-date = Move to date…
+button-open = Open
+button-remove = Remove
+button-rename = Rename
+button-submit = Submit
+
+toolbar-ics-placeholder = path/to/calendar.ics
+toolbar-import = Import .ics
+toolbar-export = Export .ics
+toolbar-tags = Tags
+toolbar-calendars = Calendars
+toolbar-undo = Undo
+toolbar-redo = Redo
+
+dialog-date = Date
+dialog-all-day = All day
+dialog-start = Start
+dialog-end = End
+dialog-end-placeholder = hh:mm or +1h30m
+dialog-repeat = Repeat
+dialog-frequency = Frequency
+dialog-every = Every
+dialog-on-days = On days
+dialog-by-day-placeholder = MO, WE or 3MO
+dialog-count = Count
+dialog-until = Until
+dialog-reminders = Reminders
+dialog-alert-placeholder = { $date } hh:mm or -P1D, -PT15M
+dialog-add-reminder = Add reminder
+dialog-tags = Tags
+dialog-description = Description
+dialog-priority = Priority
+dialog-calendar = Calendar
+
+label-priority = Priority: { $priority }
+label-tags = Tags: { $tags }
+label-reminder = Reminder: { $alert }
+label-repeats = Repeats: { $frequency }
+
+scope-question = This appointment repeats. Apply the changes to
+scope-this = This appointment
+scope-following = This and following appointments
+scope-all = All appointments
+scope-other-calendar = Only whole series can move to another calendar
+delete-question = Delete "{ $description }"?
+delete-this = Delete this appointment
+delete-following = Delete this and following appointments
+delete-all = Delete all appointments
+move-to = Move to
+
+quick-add-placeholder = Dentist tomorrow 14:30 #health !high remind 1d before
+quick-add-repeats = Repeats { $frequency } every { $interval } { $days }
+quick-add-unclear = Unclear: { $problem }
+quick-add-complete = Complete in dialog
+quick-add-open-dialog = Open dialog
+quick-add-unknown-priority = unknown priority "{ $word }"
+quick-add-two-times = more than one time given, "{ $word }"
+quick-add-two-dates = more than one date given
+quick-add-two-priorities = more than one priority given
+quick-add-two-repetitions = more than one repetition given
+quick-add-reminder-time = a reminder needs a time like "remind 1d before"
+quick-add-invalid-amount = "{ $word }" is not an amount of time that can be used
+quick-add-every = "every" needs day, week, month, year or weekdays
+quick-add-no-description = no description
+quick-add-after-midnight = the appointment ends after midnight
+quick-add-ends-before-start = the appointment ends before it starts
+quick-add-length-needs-start = a length needs a start time
+
+reminder-banner = Reminder: { $description } on { $date }, { $time }
+reminder-snooze = Snooze { $minutes } min
+
+tags-title = Tags
+tags-empty = No tags yet
+tags-new-name = New name
+tags-merge-hint = Using an existing name merges both tags
+
+history-add = Add "{ $description }"
+history-edit = Edit "{ $description }"
+history-delete = Delete "{ $description }"
+history-duplicate = Duplicate "{ $description }"
+history-move = Move "{ $description }" to { $date }
+history-copy = Copy "{ $description }" to { $date }
+history-import = Import { $count } appointments and { $tasks } tasks
+history-rename-tag = Rename tag "{ $from }" into "{ $to }"
+history-merge-tag = Merge tag "{ $from }" into "{ $to }"
+history-add-task = Add task "{ $description }"
+history-edit-task = Edit task "{ $description }"
+history-delete-task = Delete task "{ $description }"
+history-done-task = Check off "{ $description }"
+history-reopen-task = Reopen "{ $description }"
+history-checklist = Update the checklist of "{ $description }"
+
+status-save-failed = Saving failed: { $error }
+status-deleted = Deleted "{ $description }"
+status-undid = Undid: { $label }
+status-redid = Redid: { $label }
+status-restored = Restored { $path }
+status-imported = Imported { $count } appointments and { $tasks } tasks
+status-imported-skipped = Imported { $count } appointments and { $tasks } tasks, { $skipped } of them are in read-only calendars and were not changed
+status-import-failed = Import failed: { $error }
+status-exported = Exported { $count } appointments and { $tasks } tasks
+status-export-failed = Export failed: { $error }
+
+load-error-title = Your appointments could not be loaded
+load-error-restore = Restore { $path }{ $modified }
+load-error-discard = Start with an empty calendar
+error-restore = { $path } could not be restored: { $error }
+error-file-access = could not access file: { $error }
+storage-damaged = file is damaged: { $error }
+storage-newer-version = file version { $version } is newer than this version of Planer
+storage-migration = file could not be upgraded: { $error }
+ical-missing-calendar = no VCALENDAR found
+ical-unterminated = { $name } is not terminated
+ical-invalid-property = invalid property: { $line }
+
+agenda-title = Agenda
+agenda-range = Next { $days } days
+agenda-show-past = Show past ({ $count })
+agenda-hide-past = Hide past ({ $count })
+agenda-empty = Nothing coming up
+agenda-today = Today, { $date }
+agenda-tomorrow = Tomorrow, { $date }
+
+search-placeholder = Search, e.g. dentist tag:health priority:high after:1.10.2026 before:31.12.2026
+search-hint = Type to search
+search-found = { $count } found
+search-repeats = (repeats)
+query-missing-value = { $filter }: needs a value
+query-unknown-priority = unknown priority "{ $priority }", use high, middle or low
+query-invalid-date = "{ $date }" is not a date like { $format }
+
+settings-title = Settings
+settings-theme = Theme
+settings-start-view = Start with
+settings-date-format = Date format
+settings-language = Language
+settings-first-weekday = Weeks start on
+settings-data-dir = Data folder
+settings-data-dir-hint = Where saved.yml is kept, empty for the default. A folder without one gets the current appointments.
+settings-apply = Apply
+settings-default = Default
+settings-saved-in = Settings are saved in { $path }
+settings-load-failed = The settings could not be read, changes are not saved until the file is replaced: { $error }
+settings-overwrite = Replace the settings file
+settings-save-failed = The settings could not be saved: { $error }
+settings-themes-hint = Own palettes are read from { $path }
+settings-themes-failed = The palettes could not be read: { $error }
+settings-theme-missing = There is no palette named { $name } in { $path }
+theme-light = Light
+theme-dark = Dark
+theme-system = Like the system
+view-year = Year
+view-month = Month
+view-week = Week
+view-day = Day
+language-system = As the system
+week-start-language = As usual in the language
+
+tasks-title = Tasks
+tasks-new = New task
+tasks-show-done = Show done ({ $count })
+tasks-hide-done = Hide done ({ $count })
+tasks-empty = Nothing to do
+task-group-overdue = Overdue
+task-group-today = Today
+task-group-upcoming = Upcoming
+task-group-someday = Someday
+task-group-done = Done
+task-due-on = due { $date }
+task-done-on = done { $date }
+task-description = Task
+task-due = Due
+task-due-placeholder = { $format }, empty for someday
+task-checklist = Checklist
+task-add-item = Add item
+
+calendars-title = Calendars
+calendars-main = Personal
+calendars-read-only = Read-only
+calendars-read-only-hint = This calendar is read-only
+calendars-unreadable = Could not be read, changes are not saved
+calendars-new-name = New calendar
+calendars-load-failed = { $name } could not be read: { $error }
+calendars-save-failed = The calendars could not be saved: { $error }
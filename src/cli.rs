@@ -0,0 +1,591 @@
+use std::path::Path;
+
+use chrono::{Days, NaiveDate, NaiveTime, Timelike};
+use serde::Serialize;
+
+use crate::data::calendars::{self, Calendar};
+use crate::data::storage::{self, SavedData};
+use crate::data::{date, date_format, ical, new_id, Alert, Appointment, AppointmentKey, Occurrence, PDate, Priority};
+
+const USAGE: &str = "usage: planer [command]
+
+Without a command the calendar window opens.
+
+commands:
+  add <description> --date <date> [--calendar <name>] [--start <hh:mm>] [--end <hh:mm>] [--priority <high|middle|low>] [--tag <tag>]... [--remind <date hh:mm|-P1D>]...
+  list [--from <date>] [--to <date>] [--tag <tag>] [--json]
+  show <id> [--calendar <name>] [--json]
+  edit <id> [--calendar <name>] [--description <text>] [--date <date>] [--start <hh:mm>] [--end <hh:mm>] [--all-day] [--priority <priority>] [--tag <tag>]... [--remind <alert>]...
+  rm <id> [--calendar <name>]
+  agenda [--days <n>] [--json]
+  import <file.ics>
+  export <file.ics>
+
+Dates are written as dd.mm.yyyy, dd.mm.yy, mm/dd/yyyy or yyyy-mm-dd, or relative
+like today, tomorrow, +3d, -1w, +1mo, fri or next fri.
+
+list and agenda show the appointments of all calendars. Ids are counted per
+calendar, the other commands use the main calendar unless --calendar names
+another one by its name or file.";
+
+/// Runs the command given on the command line
+///
+/// Returns the exit code of the command, or `None` if there was no command and the GUI should start.
+pub fn run(args: &[String]) -> Option<i32> {
+    let command = args.first()?;
+    let args = match Args::parse(&args[1..]) {
+        Ok(args) => args,
+        Err(error) => return Some(usage(&error)),
+    };
+    let result = match (command.as_str(), args.positional.first()) {
+        ("import", Some(path)) => import(Path::new(path)),
+        ("export", Some(path)) => export(Path::new(path)),
+        ("add", Some(_)) => add(&args),
+        ("list", None) => list(&args),
+        ("show", Some(id)) => show(id, &args),
+        ("edit", Some(id)) => edit(id, &args),
+        ("rm", Some(id)) => remove(id, &args),
+        ("agenda", None) => agenda(&args),
+        ("help" | "--help" | "-h", _) => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        _ => return Some(usage(&format!("unknown command or missing argument: {}", command))),
+    };
+    match result {
+        Ok(()) => Some(0),
+        Err(CliError::Usage(error)) => Some(usage(&error)),
+        Err(CliError::Failed(error)) => {
+            eprintln!("{}", error);
+            Some(1)
+        }
+    }
+}
+
+fn usage(error: &str) -> i32 {
+    eprintln!("{}\n\n{}", error, USAGE);
+    2
+}
+
+#[derive(Debug)]
+enum CliError {
+    /// The command was called the wrong way
+    Usage(String),
+    Failed(String),
+}
+
+/// Positional arguments and `--name value` options of a command
+struct Args {
+    positional: Vec<String>,
+    options: Vec<(String, Option<String>)>,
+}
+
+/// Options that do not take a value
+const FLAGS: &[&str] = &["json", "all-day"];
+
+/// Options followed by a value
+const OPTIONS: &[&str] = &["date", "start", "end", "priority", "tag", "remind", "description", "from", "to", "days", "calendar"];
+
+impl Args {
+    fn parse(args: &[String]) -> Result<Args, String> {
+        let mut result = Args { positional: vec![], options: vec![] };
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.strip_prefix("--") {
+                Some(name) if FLAGS.contains(&name) => result.options.push((name.to_string(), None)),
+                Some(name) if OPTIONS.contains(&name) => {
+                    // An option right after it is a forgotten value, not the value
+                    let value = args.next().filter(|value| !value.starts_with("--")).ok_or(format!("--{} needs a value", name))?;
+                    result.options.push((name.to_string(), Some(value.clone())));
+                }
+                Some(name) => return Err(format!("unknown option: --{}", name)),
+                None => result.positional.push(arg.clone()),
+            }
+        }
+        Ok(result)
+    }
+
+    fn get(&self, name: &str) -> Option<&str> {
+        self.all(name).last().copied()
+    }
+
+    fn all(&self, name: &str) -> Vec<&str> {
+        self.options.iter()
+            .filter(|(option, _)| option == name)
+            .filter_map(|(_, value)| value.as_deref())
+            .collect()
+    }
+
+    fn has(&self, name: &str) -> bool {
+        self.options.iter().any(|(option, _)| option == name)
+    }
+}
+
+/// An occurrence as printed by `list` and `agenda`
+#[derive(Serialize)]
+struct Entry {
+    id: i32,
+    /// File of the calendar, `None` for the main one
+    calendar: Option<String>,
+    date: String,
+    start: Option<String>,
+    end: Option<String>,
+    description: String,
+    priority: Priority,
+    tags: Vec<String>,
+}
+
+impl Entry {
+    fn new(day: NaiveDate, occurrence: &Occurrence) -> Self {
+        let appointment = &occurrence.appointment;
+        Entry {
+            id: appointment.id,
+            calendar: appointment.calendar.clone(),
+            date: day.format("%Y-%m-%d").to_string(),
+            start: appointment.start_time().map(|time| time.format("%H:%M").to_string()),
+            end: appointment.end_time().map(|time| time.format("%H:%M").to_string()),
+            description: appointment.description().clone(),
+            priority: appointment.priority,
+            tags: appointment.tag_list().cloned().collect(),
+        }
+    }
+
+    fn line(&self) -> String {
+        let mut line = format!("{:>4}  {}  {:<13}  {}", self.id, self.date, time_string(self.start.as_deref(), self.end.as_deref()), self.description);
+        if !self.tags.is_empty() {
+            line += &format!("  #{}", self.tags.join(" #"));
+        }
+        if let Some(calendar) = &self.calendar {
+            line += &format!("  ({})", calendar);
+        }
+        line
+    }
+}
+
+fn time_string(start: Option<&str>, end: Option<&str>) -> String {
+    match (start, end) {
+        (Some(start), Some(end)) => format!("{}-{}", start, end),
+        _ => "all day".to_string(),
+    }
+}
+
+/// The calendars and the saved data with the appointments of all of them, as the calendar window loads them
+struct Data {
+    calendars: Vec<Calendar>,
+    saved: SavedData,
+}
+
+fn load() -> Result<Data, CliError> {
+    let mut saved = storage::load(&storage::data_file()).map_err(|error| CliError::Failed(format!("Could not load appointments: {}", error)))?;
+    let mut calendars = calendars::load(&calendars::calendars_file()).map_err(|error| CliError::Failed(format!("Could not load calendars: {}", error)))?;
+    for (name, error) in calendars::load_appointments(&mut calendars, &mut saved.appointments) {
+        eprintln!("Could not load calendar {}: {}", name, error);
+    }
+    Ok(Data { calendars, saved })
+}
+
+fn store(data: &Data) -> Result<(), CliError> {
+    calendars::save_appointments(&data.calendars, &data.saved.appointments, &data.saved.reminders, &data.saved.tasks)
+        .map_err(|error| CliError::Failed(format!("Could not save appointments: {}", error)))
+}
+
+/// The calendar named by `--calendar`, by its name or file, the main calendar without the option
+fn calendar<'a>(args: &Args, calendars: &'a [Calendar]) -> Result<&'a Calendar, CliError> {
+    match args.get("calendar") {
+        Some(name) => calendars.iter()
+            .find(|calendar| calendar.name == name || calendar.file == name)
+            .ok_or(CliError::Usage(format!("unknown calendar: {}", name))),
+        None => Ok(&calendars[0]),
+    }
+}
+
+/// The calendar named by `--calendar` if its appointments can be changed
+fn writable_calendar<'a>(args: &Args, calendars: &'a [Calendar]) -> Result<&'a Calendar, CliError> {
+    let calendar = calendar(args, calendars)?;
+    if !calendar.is_writable() {
+        return Err(CliError::Failed(format!("The calendar {} can not be changed", calendar)))
+    }
+    Ok(calendar)
+}
+
+/// The key of the appointment `id` of `calendar`
+fn key(id: &str, calendar: &Calendar) -> Result<AppointmentKey, CliError> {
+    Ok(AppointmentKey { calendar: (!calendar.is_main()).then(|| calendar.file.clone()), id: parse_id(id)? })
+}
+
+fn not_found(key: &AppointmentKey) -> CliError {
+    CliError::Failed(format!("No appointment with id {}", key.id))
+}
+
+fn print_json<T: Serialize>(value: &T) -> Result<(), CliError> {
+    let json = serde_json::to_string_pretty(value).map_err(|error| CliError::Failed(error.to_string()))?;
+    println!("{}", json);
+    Ok(())
+}
+
+fn parse_date(string: &str) -> Result<NaiveDate, CliError> {
+    date_format::parse_date(string, date::now().date()).ok_or(CliError::Usage(format!("invalid date: {}", string)))
+}
+
+fn parse_time(string: &str) -> Result<NaiveTime, CliError> {
+    NaiveTime::parse_from_str(string, "%H:%M").map_err(|_| CliError::Usage(format!("invalid time: {}", string)))
+}
+
+fn parse_priority(string: &str) -> Result<Priority, CliError> {
+    Priority::from_name(string).ok_or(CliError::Usage(format!("invalid priority: {}", string)))
+}
+
+/// An alert given as an offset like `-P1D` or as a date with an optional time
+fn parse_alert(string: &str) -> Result<Alert, CliError> {
+    if let Some(offset) = date::parse_iso_duration(string) {
+        return Ok(Alert::Relative(offset))
+    }
+    // The date itself may contain spaces, like "next fri 9:00"
+    let (day, time) = match (parse_date(string), string.rsplit_once(' ')) {
+        (Ok(day), _) => (day, NaiveTime::MIN),
+        (Err(_), Some((day, time))) => (parse_date(day)?, parse_time(time)?),
+        (Err(error), None) => return Err(error),
+    };
+    Ok(Alert::Absolute(date::naive_date_time_to_p_date(day.and_time(time))))
+}
+
+fn parse_id(string: &str) -> Result<i32, CliError> {
+    string.parse().map_err(|_| CliError::Usage(format!("invalid id: {}", string)))
+}
+
+/// Applies the options shared by `add` and `edit` to `appointment`
+fn apply_options(appointment: &mut Appointment, args: &Args) -> Result<(), CliError> {
+    if let Some(description) = args.get("description") {
+        appointment.description = description.to_string();
+    }
+    if let Some(day) = args.get("date") {
+        // Exceptions and changed occurrences of a series move along with it
+        let original = appointment.date;
+        let moved = appointment.moved_to(parse_date(day)?);
+        appointment.edit_series(original, moved);
+    }
+    if let Some(start) = args.get("start") {
+        // Keeps the length, but ends at midnight at the latest
+        *appointment = appointment.moved_to_time(appointment.date.date(), parse_time(start)?);
+    }
+    if let Some(end) = args.get("end") {
+        let end = parse_time(end)?;
+        if appointment.all_day || appointment.start_time().is_some_and(|start| end < start) {
+            return Err(CliError::Usage("--end needs a --start before it".to_string()))
+        }
+        appointment.end = PDate { hour: end.hour(), min: end.minute(), sec: 0, ..appointment.date };
+    }
+    if args.has("all-day") {
+        appointment.all_day = true;
+        appointment.date = PDate { hour: 0, min: 0, sec: 0, ..appointment.date };
+        appointment.end = appointment.date;
+    }
+    if let Some(priority) = args.get("priority") {
+        appointment.priority = parse_priority(priority)?;
+    }
+    let tags = args.all("tag");
+    if !tags.is_empty() {
+        appointment.tags = Some(tags.into_iter().map(str::to_string).collect());
+    }
+    let alerts = args.all("remind");
+    if !alerts.is_empty() {
+        appointment.alerts = alerts.into_iter().map(parse_alert).collect::<Result<_, _>>()?;
+    }
+    Ok(())
+}
+
+fn add(args: &Args) -> Result<(), CliError> {
+    let mut data = load()?;
+    let calendar = writable_calendar(args, &data.calendars)?;
+    let calendar = (!calendar.is_main()).then(|| calendar.file.clone());
+    let day = parse_date(args.get("date").ok_or(CliError::Usage("add needs a --date".to_string()))?)?;
+    let day = date::naive_date_time_to_p_date(day.and_time(NaiveTime::MIN));
+    let mut appointment = Appointment {
+        id: new_id(data.saved.appointments.iter().filter(|x| x.calendar == calendar)),
+        calendar,
+        date: day,
+        end: day,
+        description: args.positional.join(" "),
+        tags: Some(vec![]),
+        ..Appointment::default()
+    };
+    apply_options(&mut appointment, args)?;
+    data.saved.appointments.push(appointment.clone());
+    store(&data)?;
+    if args.has("json") {
+        return print_json(&appointment)
+    }
+    println!("Added {} with id {}", appointment.description(), appointment.id);
+    Ok(())
+}
+
+/// The occurrences from `from` to `to`, both included, with their day
+fn occurrences(appointments: &[Appointment], from: NaiveDate, to: NaiveDate) -> Vec<(NaiveDate, Occurrence)> {
+    let mut result = vec![];
+    for day in from.iter_days().take_while(|day| *day <= to) {
+        let mut occurrences: Vec<Occurrence> = appointments.iter()
+            .flat_map(|appointment| appointment.occurrences_on(day))
+            .collect();
+        occurrences.sort_by_key(|occurrence| occurrence.appointment.sort_key());
+        result.extend(occurrences.into_iter().map(|occurrence| (day, occurrence)));
+    }
+    result
+}
+
+fn list(args: &Args) -> Result<(), CliError> {
+    let appointments = load()?.saved.appointments;
+    let from = match args.get("from") {
+        Some(from) => parse_date(from)?,
+        None => date::now().date(),
+    };
+    let to = match args.get("to") {
+        Some(to) => parse_date(to)?,
+        None => from + Days::new(30),
+    };
+    let entries: Vec<Entry> = occurrences(&appointments, from, to).iter()
+        .filter(|(_, occurrence)| args.get("tag").is_none_or(|tag| occurrence.appointment.tag_list().any(|x| x == tag)))
+        .map(|(day, occurrence)| Entry::new(*day, occurrence))
+        .collect();
+    if args.has("json") {
+        return print_json(&entries)
+    }
+    for entry in entries {
+        println!("{}", entry.line());
+    }
+    Ok(())
+}
+
+fn show(id: &str, args: &Args) -> Result<(), CliError> {
+    let data = load()?;
+    let calendar = calendar(args, &data.calendars)?;
+    let key = key(id, calendar)?;
+    let appointment = data.saved.appointments.iter()
+        .find(|x| x.key() == key)
+        .ok_or(not_found(&key))?;
+    if args.has("json") {
+        return print_json(appointment)
+    }
+    println!("{}", appointment.description());
+    println!("Date:     {}", appointment.date.fmt());
+    println!("Time:     {}", appointment.time_string());
+    println!("Priority: {}", appointment.priority);
+    println!("Calendar: {}", calendar);
+    let tags: Vec<&String> = appointment.tag_list().collect();
+    if !tags.is_empty() {
+        println!("Tags:     {}", tags.iter().map(|tag| tag.as_str()).collect::<Vec<&str>>().join(", "));
+    }
+    if let Some(recurrence) = &appointment.recurrence {
+        println!("Repeats:  {}", recurrence.to_rrule());
+    }
+    for alert in &appointment.alerts {
+        println!("Reminder: {}", alert);
+    }
+    Ok(())
+}
+
+fn edit(id: &str, args: &Args) -> Result<(), CliError> {
+    let mut data = load()?;
+    let key = key(id, writable_calendar(args, &data.calendars)?)?;
+    let appointment = data.saved.appointments.iter_mut()
+        .find(|x| x.key() == key)
+        .ok_or(not_found(&key))?;
+    apply_options(appointment, args)?;
+    let description = appointment.description().clone();
+    store(&data)?;
+    println!("Changed {}", description);
+    Ok(())
+}
+
+fn remove(id: &str, args: &Args) -> Result<(), CliError> {
+    let mut data = load()?;
+    let key = key(id, writable_calendar(args, &data.calendars)?)?;
+    let index = data.saved.appointments.iter()
+        .position(|x| x.key() == key)
+        .ok_or(not_found(&key))?;
+    let removed = data.saved.appointments.remove(index);
+    store(&data)?;
+    println!("Deleted {}", removed.description());
+    Ok(())
+}
+
+fn agenda(args: &Args) -> Result<(), CliError> {
+    let appointments = load()?.saved.appointments;
+    let days = match args.get("days") {
+        Some(days) => days.parse::<u64>().map_err(|_| CliError::Usage(format!("invalid number of days: {}", days)))?,
+        None => 7,
+    };
+    let today = date::now().date();
+    let last = today.checked_add_days(Days::new(days.saturating_sub(1)))
+        .ok_or(CliError::Usage(format!("invalid number of days: {}", days)))?;
+    let occurrences = occurrences(&appointments, today, last);
+    if args.has("json") {
+        let entries: Vec<Entry> = occurrences.iter().map(|(day, occurrence)| Entry::new(*day, occurrence)).collect();
+        return print_json(&entries)
+    }
+    let mut current = None;
+    for (day, occurrence) in &occurrences {
+        if current != Some(*day) {
+            println!("{}", date_format::format_long(*day));
+            current = Some(*day);
+        }
+        let entry = Entry::new(*day, occurrence);
+        println!("  {:<13}  {}", time_string(entry.start.as_deref(), entry.end.as_deref()), entry.description);
+    }
+    if occurrences.is_empty() {
+        println!("Nothing coming up");
+    }
+    Ok(())
+}
+
+fn import(path: &Path) -> Result<(), CliError> {
+    let mut data = load()?;
+    let imported = ical::import_file(path).map_err(|error| CliError::Failed(format!("Import failed: {}", error)))?;
+    let (count, tasks) = (imported.appointments.len(), imported.tasks.len());
+    let calendars = &data.calendars;
+    let skipped = ical::merge(imported, &mut data.saved.appointments, &mut data.saved.tasks, |appointment| {
+        calendars::calendar_of(calendars, appointment).is_none_or(Calendar::is_writable)
+    });
+    store(&data)?;
+    println!("Imported {} appointments and {} tasks", count, tasks);
+    if skipped > 0 {
+        println!("{} of them are in calendars that can not be changed and were left as they are", skipped);
+    }
+    Ok(())
+}
+
+fn export(path: &Path) -> Result<(), CliError> {
+    let data = load()?.saved;
+    ical::export_file(path, &data.appointments, &data.tasks).map_err(|error| CliError::Failed(format!("Export failed: {}", error)))?;
+    println!("Exported {} appointments and {} tasks", data.appointments.len(), data.tasks.len());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Recurrence;
+    use serde_json::json;
+
+    fn parse(args: &[&str]) -> Result<Args, String> {
+        Args::parse(&args.iter().map(|arg| arg.to_string()).collect::<Vec<String>>())
+    }
+
+    #[test]
+    fn parses_options() {
+        let args = parse(&["Dentist", "appointment", "--tag", "health", "--json", "--remind", "-P1D", "--tag", "teeth", "--remind", "tomorrow 9:00", "--date", "today", "--date", "tomorrow"]).unwrap();
+        assert_eq!(args.positional, vec!["Dentist", "appointment"]);
+        assert_eq!(args.all("tag"), vec!["health", "teeth"]);
+        assert_eq!(args.all("remind"), vec!["-P1D", "tomorrow 9:00"]);
+        // The last of an option given twice counts
+        assert_eq!(args.get("date"), Some("tomorrow"));
+        assert!(args.has("json"));
+        assert!(!args.has("all-day"));
+        assert_eq!(args.get("priority"), None);
+    }
+
+    #[test]
+    fn rejects_missing_values_and_unknown_options() {
+        assert_eq!(parse(&["--date"]).err(), Some("--date needs a value".to_string()));
+        assert_eq!(parse(&["--tag", "--json"]).err(), Some("--tag needs a value".to_string()));
+        assert_eq!(parse(&["--colour", "red"]).err(), Some("unknown option: --colour".to_string()));
+    }
+
+    #[test]
+    fn rejects_invalid_values() {
+        let mut appointment = Appointment::default();
+        for (option, value) in [("priority", "urgent"), ("date", "32.13.2026"), ("start", "25:00"), ("remind", "someday 9:00")] {
+            let args = parse(&[&format!("--{}", option), value]).unwrap();
+            assert!(matches!(apply_options(&mut appointment, &args), Err(CliError::Usage(_))), "--{} {}", option, value);
+        }
+        assert_eq!(appointment, Appointment::default());
+        assert!(matches!(parse_id("first"), Err(CliError::Usage(_))));
+    }
+
+    #[test]
+    fn applies_options() {
+        let mut appointment = Appointment::default();
+        let args = parse(&["--date", "2026-10-20", "--start", "9:30", "--end", "11:00", "--priority", "high", "--tag", "a", "--tag", "b", "--remind", "-PT15M"]).unwrap();
+        apply_options(&mut appointment, &args).unwrap();
+        assert_eq!((appointment.date, appointment.end), (PDate::new(2026, 10, 20, 9, 30, 0), PDate::new(2026, 10, 20, 11, 0, 0)));
+        assert_eq!(appointment.priority, Priority::High);
+        assert_eq!(appointment.tags, Some(vec!["a".to_string(), "b".to_string()]));
+        assert_eq!(appointment.alerts, vec![Alert::Relative(chrono::Duration::minutes(-15))]);
+    }
+
+    #[test]
+    fn moves_exceptions_with_the_series() {
+        let mut series = Appointment {
+            date: PDate::new(2026, 10, 19, 9, 0, 0),
+            end: PDate::new(2026, 10, 19, 10, 0, 0),
+            all_day: false,
+            recurrence: Some(Recurrence::default()),
+            exceptions: vec![PDate::new(2026, 10, 26, 9, 0, 0)],
+            ..Appointment::default()
+        };
+        apply_options(&mut series, &parse(&["--date", "2026-10-21"]).unwrap()).unwrap();
+        assert_eq!(series.date, PDate::new(2026, 10, 21, 9, 0, 0));
+        assert_eq!(series.exceptions, vec![PDate::new(2026, 10, 28, 9, 0, 0)]);
+    }
+
+    #[test]
+    fn keeps_appointments_within_the_day() {
+        let mut appointment = Appointment {
+            date: PDate::new(2026, 10, 19, 9, 0, 0),
+            end: PDate::new(2026, 10, 19, 12, 0, 0),
+            all_day: false,
+            ..Appointment::default()
+        };
+        apply_options(&mut appointment, &parse(&["--start", "22:00"]).unwrap()).unwrap();
+        assert_eq!((appointment.date, appointment.end), (PDate::new(2026, 10, 19, 22, 0, 0), PDate::new(2026, 10, 19, 23, 59, 0)));
+    }
+
+    #[test]
+    fn prints_entries_as_json() {
+        let appointment = Appointment {
+            id: 4,
+            date: PDate::new(2026, 10, 19, 9, 0, 0),
+            end: PDate::new(2026, 10, 19, 10, 30, 0),
+            all_day: false,
+            tags: Some(vec!["work".to_string(), "".to_string()]),
+            description: "Standup".to_string(),
+            recurrence: Some(Recurrence::default()),
+            calendar: Some("work.yml".to_string()),
+            ..Appointment::default()
+        };
+        let from = NaiveDate::from_ymd_opt(2026, 10, 19).unwrap();
+        let entries: Vec<Entry> = occurrences(&[appointment, Appointment { id: 5, ..Appointment::default() }], from, from + Days::new(7)).iter()
+            .map(|(day, occurrence)| Entry::new(*day, occurrence))
+            .collect();
+        assert_eq!(serde_json::to_value(&entries).unwrap(), json!([
+            { "id": 4, "calendar": "work.yml", "date": "2026-10-19", "start": "09:00", "end": "10:30", "description": "Standup", "priority": "Low", "tags": ["work"] },
+            { "id": 4, "calendar": "work.yml", "date": "2026-10-26", "start": "09:00", "end": "10:30", "description": "Standup", "priority": "Low", "tags": ["work"] },
+        ]));
+
+        let all_day = serde_json::to_value(Entry::new(from, &Appointment::default().occurrence(PDate::default()))).unwrap();
+        assert_eq!((&all_day["start"], &all_day["calendar"]), (&serde_json::Value::Null, &serde_json::Value::Null));
+    }
+
+    #[test]
+    fn picks_calendars_by_name_or_file() {
+        let calendars = vec![
+            Calendar::default(),
+            Calendar { name: "Work".to_string(), file: "work.yml".to_string(), ..Calendar::default() },
+            Calendar { name: "Holidays".to_string(), file: "holidays.yml".to_string(), read_only: true, ..Calendar::default() },
+        ];
+        let key_in = |options: &[&str]| key("3", calendar(&parse(options).unwrap(), &calendars).unwrap()).unwrap();
+        assert_eq!(key_in(&[]), AppointmentKey { calendar: None, id: 3 });
+        assert_eq!(key_in(&["--calendar", "Work"]), AppointmentKey { calendar: Some("work.yml".to_string()), id: 3 });
+        assert_eq!(key_in(&["--calendar", "work.yml"]), AppointmentKey { calendar: Some("work.yml".to_string()), id: 3 });
+        assert!(matches!(calendar(&parse(&["--calendar", "Home"]).unwrap(), &calendars), Err(CliError::Usage(_))));
+        assert!(matches!(writable_calendar(&parse(&["--calendar", "Holidays"]).unwrap(), &calendars), Err(CliError::Failed(_))));
+    }
+
+    #[test]
+    fn shows_appointments_as_json() {
+        let value = serde_json::to_value(Appointment { id: 2, tags: Some(vec!["a".to_string()]), ..Appointment::default() }).unwrap();
+        let keys: Vec<&String> = value.as_object().unwrap().keys().collect();
+        assert_eq!(keys, ["alerts", "all_day", "date", "description", "end", "exceptions", "id", "overrides", "priority", "recurrence", "tags", "uid"]);
+        assert_eq!(value["date"], json!({ "year": 1970, "month": 1, "day": 1, "hour": 0, "min": 0, "sec": 0 }));
+        assert_eq!((value["id"].clone(), value["tags"].clone()), (json!(2), json!(["a"])));
+    }
+}
@@ -0,0 +1,216 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::reminder::Acknowledged;
+use super::storage::{self, StorageError};
+use super::theme::Rgb;
+use super::{Appointment, Task};
+
+/// File of the main calendar, it also holds the tasks and reminder states
+pub const MAIN_FILE: &str = "saved.yml";
+
+/// Colors given to new calendars in turn
+const COLORS: &[Rgb] = &[
+    Rgb(0x35, 0x84, 0xe4),
+    Rgb(0x2e, 0xc2, 0x7e),
+    Rgb(0xf6, 0xd3, 0x2d),
+    Rgb(0xe6, 0x61, 0x00),
+    Rgb(0x91, 0x41, 0xac),
+    Rgb(0xe0, 0x1b, 0x24),
+];
+
+/// A named calendar with its appointments in a file of its own
+///
+/// The calendars are listed in `calendars.yml` next to the data file:
+///
+/// ```yaml
+/// - name: Work
+///   file: work.yml
+///   color: "#3584e4"
+///   visible: true
+///   read_only: false
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct Calendar {
+    pub name: String,
+    /// File of the appointments, relative to the data directory
+    pub file: String,
+    pub color: Rgb,
+    pub visible: bool,
+    /// Planer never writes the file, for calendars kept up to date by someone else
+    pub read_only: bool,
+    /// The file could not be read, so it is not written either
+    #[serde(skip)]
+    pub unreadable: bool,
+}
+
+impl Default for Calendar {
+    fn default() -> Self {
+        Calendar { name: t!("calendars-main"), file: MAIN_FILE.to_string(), color: COLORS[0], visible: true, read_only: false, unreadable: false }
+    }
+}
+
+impl Calendar {
+    pub fn is_main(&self) -> bool {
+        self.file == MAIN_FILE
+    }
+
+    /// Whether appointments can be added to the calendar and changed
+    pub fn is_writable(&self) -> bool {
+        !self.read_only && !self.unreadable
+    }
+
+    pub fn path(&self) -> PathBuf {
+        storage::data_dir().join(&self.file)
+    }
+}
+
+impl fmt::Display for Calendar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.name.fmt(f)
+    }
+}
+
+/// The list of the calendars, in the data directory
+pub fn calendars_file() -> PathBuf {
+    storage::data_dir().join("calendars.yml")
+}
+
+/// Reads the calendars from `path`, the main calendar comes first and is added if it is missing
+pub fn load(path: &Path) -> Result<Vec<Calendar>, StorageError> {
+    let calendars: Vec<Calendar> = match fs::read_to_string(path) {
+        Ok(text) if text.trim().is_empty() => vec![],
+        Ok(text) => serde_yaml::from_str(&text)?,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => vec![],
+        Err(error) => return Err(error.into()),
+    };
+    Ok(with_main(calendars))
+}
+
+fn with_main(mut calendars: Vec<Calendar>) -> Vec<Calendar> {
+    let main = match calendars.iter().position(Calendar::is_main) {
+        // The main calendar always takes changes, it holds the tasks
+        Some(index) => Calendar { read_only: false, ..calendars.remove(index) },
+        None => Calendar::default(),
+    };
+    let mut all = vec![main];
+    all.append(&mut calendars);
+    all
+}
+
+pub fn save(path: &Path, calendars: &[Calendar]) -> Result<(), StorageError> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    storage::write_atomically(path, &serde_yaml::to_string(calendars)?)
+}
+
+/// Adds the appointments of all calendars but the main one to `appointments`
+///
+/// Calendars that could not be read are marked as unreadable and returned with the reason.
+pub fn load_appointments(calendars: &mut [Calendar], appointments: &mut Vec<Appointment>) -> Vec<(String, StorageError)> {
+    let mut errors = vec![];
+    for calendar in calendars.iter_mut().filter(|x| !x.is_main()) {
+        match storage::load(&calendar.path()) {
+            Ok(data) => {
+                // Ids are kept as they are, the calendar tells them apart from those of the others
+                appointments.extend(data.appointments.into_iter().map(|appointment| Appointment { calendar: Some(calendar.file.clone()), ..appointment }));
+            }
+            Err(error) => {
+                calendar.unreadable = true;
+                errors.push((calendar.name.clone(), error));
+            }
+        }
+    }
+    errors
+}
+
+/// Writes the appointments into the files of their calendars, the reminder states and tasks go into the main one
+///
+/// Calendars that are not writable are left alone, appointments of calendars that
+/// were removed from the list are kept in the main calendar.
+pub fn save_appointments(calendars: &[Calendar], appointments: &[Appointment], reminders: &[Acknowledged], tasks: &[Task]) -> Result<(), StorageError> {
+    let in_file = |appointment: &&Appointment, file: Option<&String>| match &appointment.calendar {
+        Some(calendar) if calendars.iter().any(|x| x.file == *calendar) => Some(calendar) == file,
+        _ => file.is_none(),
+    };
+    let main: Vec<Appointment> = appointments.iter().filter(|x| in_file(x, None)).cloned().collect();
+    let mut result = storage::save(&storage::data_file(), &main, reminders, tasks);
+    for calendar in calendars.iter().filter(|x| !x.is_main() && x.is_writable()) {
+        let appointments: Vec<Appointment> = appointments.iter().filter(|x| in_file(x, Some(&calendar.file))).cloned().collect();
+        result = result.and(storage::save(&calendar.path(), &appointments, &[], &[]));
+    }
+    result
+}
+
+/// The calendar `appointment` belongs to
+pub fn calendar_of<'a>(calendars: &'a [Calendar], appointment: &Appointment) -> Option<&'a Calendar> {
+    let file = appointment.calendar.as_deref().unwrap_or(MAIN_FILE);
+    calendars.iter().find(|calendar| calendar.file == file)
+}
+
+/// A new calendar named `name` with a file name and color not used by `calendars` yet
+pub fn new_calendar(name: &str, calendars: &[Calendar]) -> Calendar {
+    let stem = slug(name);
+    let taken = |file: &str| {
+        calendars.iter().any(|calendar| calendar.file == file)
+            || [MAIN_FILE, "calendars.yml", "config.yml", "themes.yml"].contains(&file)
+    };
+    let file = (1..)
+        .map(|number| if number == 1 { format!("{}.yml", stem) } else { format!("{}-{}.yml", stem, number) })
+        .find(|file| !taken(file))
+        .unwrap();
+    Calendar {
+        name: name.trim().to_string(),
+        file,
+        color: COLORS[calendars.len() % COLORS.len()],
+        ..Calendar::default()
+    }
+}
+
+/// The words of the name in lower case joined by dashes
+fn slug(name: &str) -> String {
+    let slug = name.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<&str>>()
+        .join("-");
+    if slug.is_empty() { "calendar".to_string() } else { slug }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn main_calendar_comes_first() {
+        let calendars: Vec<Calendar> = serde_yaml::from_str("- name: Work\n  file: work.yml\n  color: \"#3584e4\"\n  read_only: true\n").unwrap();
+        let calendars = with_main(calendars);
+        assert_eq!(calendars.len(), 2);
+        assert!(calendars[0].is_main() && calendars[0].is_writable());
+        assert!(calendars[1].visible && !calendars[1].is_writable());
+
+        let calendars: Vec<Calendar> = serde_yaml::from_str("- name: Team\n  file: team.yml\n- name: Mine\n  file: saved.yml\n  read_only: true\n").unwrap();
+        let calendars = with_main(calendars);
+        assert_eq!(calendars[0].name, "Mine");
+        assert!(calendars[0].is_writable());
+    }
+
+    #[test]
+    fn new_calendars_get_free_files() {
+        let calendars = vec![Calendar::default()];
+        let work = new_calendar(" Work / Team ", &calendars);
+        assert_eq!(work.name, "Work / Team");
+        assert_eq!(work.file, "work-team.yml");
+        assert_ne!(work.color, calendars[0].color);
+        let calendars = vec![Calendar::default(), work];
+        assert_eq!(new_calendar("work / team", &calendars).file, "work-team-2.yml");
+        assert_eq!(new_calendar("Saved", &calendars).file, "saved-2.yml");
+        assert_eq!(new_calendar("!!", &calendars).file, "calendar.yml");
+    }
+}
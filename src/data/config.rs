@@ -0,0 +1,255 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use chrono::Weekday;
+use serde::{Deserialize, Serialize};
+
+use super::date_format::{self, DateFormat};
+use super::file_path;
+use super::storage::{self, StorageError};
+use crate::i18n::{self, Locale};
+
+/// The settings of the user, saved in `config.yml`
+///
+/// Missing keys take their default, so older files keep working.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(default)]
+pub struct Config {
+    /// Directory of `saved.yml`, the directory of the config file if not set
+    pub data_dir: Option<PathBuf>,
+    pub theme: ThemeChoice,
+    pub start_view: StartView,
+    pub date_format: DateFormat,
+    pub language: Language,
+    pub first_weekday: WeekStart,
+}
+
+/// Saved as `light`, `dark`, `system` or the name of a palette in `themes.yml`
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+#[serde(from = "String", into = "String")]
+pub enum ThemeChoice {
+    #[default]
+    Light,
+    Dark,
+    /// Light or dark as preferred by the desktop
+    System,
+    /// A palette of the user by its name
+    Custom(String),
+}
+
+impl ThemeChoice {
+    pub const BUILT_IN: &[Self] = &[Self::Light, Self::Dark, Self::System];
+}
+
+impl From<String> for ThemeChoice {
+    fn from(name: String) -> Self {
+        match name.as_str() {
+            "light" => ThemeChoice::Light,
+            "dark" => ThemeChoice::Dark,
+            "system" => ThemeChoice::System,
+            _ => ThemeChoice::Custom(name),
+        }
+    }
+}
+
+impl From<ThemeChoice> for String {
+    fn from(theme: ThemeChoice) -> Self {
+        match theme {
+            ThemeChoice::Light => "light".to_string(),
+            ThemeChoice::Dark => "dark".to_string(),
+            ThemeChoice::System => "system".to_string(),
+            ThemeChoice::Custom(name) => name,
+        }
+    }
+}
+
+impl fmt::Display for ThemeChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThemeChoice::Light => t!("theme-light"),
+            ThemeChoice::Dark => t!("theme-dark"),
+            ThemeChoice::System => t!("theme-system"),
+            ThemeChoice::Custom(name) => name.clone(),
+        }
+        .fmt(f)
+    }
+}
+
+/// The view the calendar opens with
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum StartView {
+    Year,
+    #[default]
+    Month,
+    Week,
+    Day,
+}
+
+impl StartView {
+    pub const ALL: &[Self] = &[Self::Year, Self::Month, Self::Week, Self::Day];
+}
+
+impl fmt::Display for StartView {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StartView::Year => t!("view-year"),
+            StartView::Month => t!("view-month"),
+            StartView::Week => t!("view-week"),
+            StartView::Day => t!("view-day"),
+        }
+        .fmt(f)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    /// The language set in the environment
+    #[default]
+    System,
+    English,
+    German,
+}
+
+impl Language {
+    pub const ALL: &[Self] = &[Self::System, Self::English, Self::German];
+
+    pub fn locale(&self) -> Locale {
+        match self {
+            Language::System => Locale::detect(),
+            Language::English => Locale::English,
+            Language::German => Locale::German,
+        }
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Language::System => write!(f, "{}", t!("language-system")),
+            Language::English => Locale::English.fmt(f),
+            Language::German => Locale::German.fmt(f),
+        }
+    }
+}
+
+/// The day weeks start with in the calendar
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum WeekStart {
+    /// The usual first day in the language
+    #[default]
+    Language,
+    Monday,
+    Saturday,
+    Sunday,
+}
+
+impl WeekStart {
+    pub const ALL: &[Self] = &[Self::Language, Self::Monday, Self::Saturday, Self::Sunday];
+
+    pub fn weekday(&self) -> Option<Weekday> {
+        match self {
+            WeekStart::Language => None,
+            WeekStart::Monday => Some(Weekday::Mon),
+            WeekStart::Saturday => Some(Weekday::Sat),
+            WeekStart::Sunday => Some(Weekday::Sun),
+        }
+    }
+}
+
+impl fmt::Display for WeekStart {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.weekday() {
+            Some(weekday) => write!(f, "{}", i18n::weekday_name(weekday)),
+            None => write!(f, "{}", t!("week-start-language")),
+        }
+    }
+}
+
+impl Config {
+    /// Makes the settings take effect for everything shown or saved from now on
+    pub fn apply(&self) {
+        storage::set_data_dir(self.data_dir.clone());
+        date_format::set_display_format(self.date_format);
+        i18n::set_locale(self.language.locale());
+        i18n::set_first_weekday(self.first_weekday.weekday());
+    }
+}
+
+/// The file the settings are saved in, always in the default directory so it can be found
+pub fn config_file() -> PathBuf {
+    file_path().join("config.yml")
+}
+
+/// Reads the settings from `path`, a missing file holds the defaults
+pub fn load(path: &Path) -> Result<Config, StorageError> {
+    match fs::read_to_string(path) {
+        Ok(text) if text.trim().is_empty() => Ok(Config::default()),
+        Ok(text) => Ok(serde_yaml::from_str(&text)?),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Config::default()),
+        Err(error) => Err(error.into()),
+    }
+}
+
+pub fn save(path: &Path, config: &Config) -> Result<(), StorageError> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    storage::write_atomically(path, &serde_yaml::to_string(config)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_keys_take_defaults() {
+        let config: Config = serde_yaml::from_str("theme: dark\nfirst_weekday: sunday\n").unwrap();
+        assert_eq!(config, Config { theme: ThemeChoice::Dark, first_weekday: WeekStart::Sunday, ..Config::default() });
+        assert_eq!(config.start_view, StartView::Month);
+    }
+
+    #[test]
+    fn themes_are_names() {
+        let config: Config = serde_yaml::from_str("theme: Solarized\n").unwrap();
+        assert_eq!(config.theme, ThemeChoice::Custom("Solarized".to_string()));
+        assert!(serde_yaml::to_string(&config).unwrap().contains("theme: Solarized"));
+        let config: Config = serde_yaml::from_str("theme: system\n").unwrap();
+        assert_eq!(config.theme, ThemeChoice::System);
+    }
+
+    #[test]
+    fn round_trips() {
+        let config = Config {
+            data_dir: Some(PathBuf::from("/tmp/planer")),
+            theme: ThemeChoice::Dark,
+            start_view: StartView::Week,
+            date_format: DateFormat::Iso,
+            language: Language::German,
+            first_weekday: WeekStart::Monday,
+        };
+        let text = serde_yaml::to_string(&config).unwrap();
+        assert!(text.contains("language: german") && text.contains("date_format: iso"));
+        assert_eq!(serde_yaml::from_str::<Config>(&text).unwrap(), config);
+    }
+
+    #[test]
+    fn saves_and_loads() {
+        let dir = std::env::temp_dir().join(format!("planer-config-{}", std::process::id()));
+        let path = dir.join("config.yml");
+        assert_eq!(load(&path).unwrap(), Config::default());
+
+        let config = Config { start_view: StartView::Day, ..Config::default() };
+        save(&path, &config).unwrap();
+        assert_eq!(load(&path).unwrap(), config);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+
+        fs::write(&path, "start_view: [").unwrap();
+        assert!(load(&path).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
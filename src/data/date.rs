@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use chrono::naive::{NaiveDateTime, NaiveDate};
-use chrono::{Datelike, Timelike, NaiveTime, Days};
+use chrono::{Datelike, Timelike, NaiveTime, Duration};
+
+use super::date_format;
 
 
 #[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq)]
@@ -33,71 +35,133 @@ impl PDate {
     }
 
     pub fn day_string(self) -> String {
-        format!("{}.{}", self.day, self.month)
+        date_format::format_day_month(self.date())
     }
 
     pub fn fmt(self) -> String {
-        format!("{}.{}.{}", self.day, self.month, self.year)
+        date_format::format_date(self.date())
     }
 
-}
+    /// The day of this date without the time
+    pub fn date(self) -> NaiveDate {
+        NaiveDate::from_ymd_opt(self.year, self.month, self.day).unwrap()
+    }
 
-pub fn now() -> NaiveDateTime {
-    chrono::offset::Local::now().date_naive().and_hms_opt(0, 0, 0).unwrap()
-}
+    /// The same time of day on another date
+    pub fn with_date(self, date: NaiveDate) -> Self {
+        Self { year: date.year(), month: date.month(), day: date.day(), ..self }
+    }
 
-pub fn new_date( year: i32, month: u32, day: u32 ) -> NaiveDate {
-    NaiveDate::from_ymd_opt(year, month, day).unwrap()
 }
 
-pub fn new_time( year: i32, month: u32, day: u32, hour: u32, min: u32, sec: u32 ) -> NaiveDateTime {
-    NaiveDate::from_ymd_opt(year, month, day).unwrap().and_hms_opt(hour, min, sec).unwrap()
+pub fn now() -> NaiveDateTime {
+    chrono::offset::Local::now().date_naive().and_hms_opt(0, 0, 0).unwrap()
 }
 
-pub fn format_dmy( date: NaiveDateTime ) -> String {
-    format!("{}.{}.{}", date.day(), date.month(), date.year())
+pub fn day_string( date: &NaiveDateTime ) -> String {
+    date_format::format_day_month(date.date())
 }
 
-pub fn first_day_in_month( date: NaiveDateTime ) -> i32 {
-    let nd = NaiveDate::from_ymd_opt( date.year(), date.month(), 1)
-        .unwrap();
-    nd
-        .weekday()
-        .num_days_from_monday() as i32
+pub fn naive_date_time_to_p_date( date: NaiveDateTime ) -> PDate {
+    PDate { year: date.year(), month: date.month(), day: date.day(), hour: date.hour(), min: date.minute(), sec: date.second() }
 }
 
-pub fn check_last_day_in_month( date: NaiveDateTime ) -> bool {
-    date.checked_add_days(Days::new(1)).unwrap().day() == 1
+pub fn p_date_to_naive_date_time( date: PDate ) -> NaiveDateTime {
+    NaiveDateTime::new(NaiveDate::from_ymd_opt(date.year, date.month, date.day).unwrap(), NaiveTime::from_hms_opt(date.hour, date.min, date.sec).unwrap())
 }
 
-pub fn last_day_in_month( date: NaiveDateTime ) -> i32 {
-    NaiveDate::from_ymd_opt(date.year(), date.month() + 1, 1)
-        .unwrap_or(NaiveDate::from_ymd_opt(date.year() + 1, 1, 1).unwrap())
-        .pred_opt()
-        .unwrap()
-        .weekday()
-        .num_days_from_monday() as i32
+pub fn naive_date_time_as_string( date: NaiveDateTime ) -> String {
+    date_format::format_date(date.date())
 }
 
-pub fn days_in_month( date: NaiveDateTime ) -> i32 {
-    NaiveDate::from_ymd_opt(date.year(), date.month() + 1, 1)
-        .unwrap_or(NaiveDate::from_ymd_opt(date.year() + 1, 1, 1).unwrap())
-        .signed_duration_since(NaiveDate::from_ymd_opt(date.year(), date.month(), 1).unwrap())
-        .num_days() as i32
+/// Parses an ISO 8601 duration like `P1D`, `-PT15M` or `P1W`
+pub fn parse_iso_duration( string: &str ) -> Option<Duration> {
+    let string = string.trim();
+    let (sign, rest) = match string.strip_prefix('-') {
+        Some(rest) => (-1, rest),
+        None => (1, string.strip_prefix('+').unwrap_or(string)),
+    };
+    let rest = rest.strip_prefix('P')?;
+    let mut seconds: i64 = 0;
+    let mut number = String::new();
+    let mut in_time = false;
+    let mut empty = true;
+    for c in rest.chars() {
+        let unit = match (c, in_time) {
+            ('0'..='9', _) => {
+                number.push(c);
+                continue
+            }
+            ('T', false) if number.is_empty() => {
+                in_time = true;
+                continue
+            }
+            ('W', false) => 7 * 24 * 3600,
+            ('D', false) => 24 * 3600,
+            ('H', true) => 3600,
+            ('M', true) => 60,
+            ('S', true) => 1,
+            _ => return None
+        };
+        seconds = seconds.checked_add(number.parse::<i64>().ok()?.checked_mul(unit)?)?;
+        number.clear();
+        empty = false;
+    }
+    if empty || !number.is_empty() {
+        return None
+    }
+    Duration::try_seconds(sign * seconds)
 }
 
-pub fn day_string( date: &NaiveDateTime ) -> String {
-    format!("{}.{}", date.day(), date.month())
+/// Formats a duration the way [`parse_iso_duration`] reads it, like `-P1D` or `-PT1H30M`
+pub fn format_iso_duration( duration: Duration ) -> String {
+    let sign = if duration < Duration::zero() { "-" } else { "" };
+    let seconds = duration.num_seconds().abs();
+    let (days, rest) = (seconds / 86400, seconds % 86400);
+    if rest == 0 && days > 0 && days % 7 == 0 {
+        return format!("{}P{}W", sign, days / 7)
+    }
+    let mut string = format!("{}P", sign);
+    if days > 0 {
+        string += &format!("{}D", days);
+    }
+    if rest > 0 || days == 0 {
+        string.push('T');
+        let (hours, minutes, seconds) = (rest / 3600, rest % 3600 / 60, rest % 60);
+        if hours > 0 {
+            string += &format!("{}H", hours);
+        }
+        if minutes > 0 {
+            string += &format!("{}M", minutes);
+        }
+        if seconds > 0 || rest == 0 {
+            string += &format!("{}S", seconds);
+        }
+    }
+    string
 }
 
-pub fn naive_date_time_to_p_date( date: NaiveDateTime ) -> PDate {
-    PDate { year: date.year(), month: date.month(), day: date.day(), hour: date.hour(), min: date.minute(), sec: date.second() }
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_iso_durations() {
+        assert_eq!(parse_iso_duration("P1D"), Some(Duration::days(1)));
+        assert_eq!(parse_iso_duration("-PT1H30M"), Some(Duration::minutes(-90)));
+        assert_eq!(parse_iso_duration("P2W"), Some(Duration::weeks(2)));
+        assert_eq!(parse_iso_duration("PT"), None);
+        assert_eq!(parse_iso_duration("P1H"), None);
+        for duration in [Duration::minutes(-15), Duration::days(1), Duration::seconds(90061), Duration::zero()] {
+            assert_eq!(parse_iso_duration(&format_iso_duration(duration)), Some(duration));
+        }
+    }
 
-pub fn p_date_to_naive_date_time( date: PDate ) -> NaiveDateTime {
-    NaiveDateTime::new(NaiveDate::from_ymd_opt(date.year, date.month, date.day).unwrap(), NaiveTime::from_hms_opt(date.hour, date.min, date.sec).unwrap())
+    #[test]
+    fn rejects_iso_durations_out_of_range() {
+        assert_eq!(parse_iso_duration("P99999999999999999D"), None);
+        assert_eq!(parse_iso_duration("P1000000000000D"), None);
+        assert_eq!(parse_iso_duration("PT99999999999999999999S"), None);
+        assert_eq!(parse_iso_duration("P100000000000W1D"), None);
+    }
 }
-
-pub fn naive_date_time_as_string( date: NaiveDateTime ) -> String {
-    format!("{}.{}.{}", date.day(), date.month(), date.year())
-}
\ No newline at end of file
@@ -0,0 +1,266 @@
+use std::fmt;
+use std::sync::RwLock;
+
+use chrono::{Datelike, Days, Months, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+
+use crate::i18n;
+
+/// How dates are shown to the user
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DateFormat {
+    /// 31.12.2026
+    #[default]
+    DayMonthYear,
+    /// 12/31/2026
+    MonthDayYear,
+    /// 2026-12-31
+    Iso,
+}
+
+impl DateFormat {
+    pub const ALL: &[Self] = &[Self::DayMonthYear, Self::MonthDayYear, Self::Iso];
+
+    fn pattern(&self) -> &'static str {
+        match self {
+            DateFormat::DayMonthYear => "%-d.%-m.%Y",
+            DateFormat::MonthDayYear => "%-m/%-d/%Y",
+            DateFormat::Iso => "%Y-%m-%d",
+        }
+    }
+
+    /// Pattern of a date without the year
+    fn short_pattern(&self) -> &'static str {
+        match self {
+            DateFormat::DayMonthYear => "%-d.%-m.",
+            DateFormat::MonthDayYear => "%-m/%-d",
+            DateFormat::Iso => "%m-%d",
+        }
+    }
+
+    /// Hint shown in empty date inputs
+    pub fn placeholder(&self) -> &'static str {
+        match self {
+            DateFormat::DayMonthYear => "dd.mm.yyyy",
+            DateFormat::MonthDayYear => "mm/dd/yyyy",
+            DateFormat::Iso => "yyyy-mm-dd",
+        }
+    }
+}
+
+impl fmt::Display for DateFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.placeholder())
+    }
+}
+
+/// The format chosen in the config, shared by all views
+static DISPLAY_FORMAT: RwLock<DateFormat> = RwLock::new(DateFormat::DayMonthYear);
+
+pub fn display_format() -> DateFormat {
+    *DISPLAY_FORMAT.read().unwrap()
+}
+
+pub fn set_display_format(format: DateFormat) {
+    *DISPLAY_FORMAT.write().unwrap() = format;
+}
+
+/// The date in the display format, like `31.12.2026`
+pub fn format_date(date: NaiveDate) -> String {
+    date.format(display_format().pattern()).to_string()
+}
+
+/// Day and month in the display format, like `31.12.`
+pub fn format_day_month(date: NaiveDate) -> String {
+    date.format(display_format().short_pattern()).to_string()
+}
+
+/// The weekday followed by the date, like `Thursday, 31.12.2026`
+pub fn format_long(date: NaiveDate) -> String {
+    t!("date-long", weekday = i18n::weekday_name(date.weekday()), date = format_date(date))
+}
+
+/// Reads a date typed by the user
+///
+/// Understands ISO 8601 (`2026-12-31`), `12/31/2026`, `31.12.2026`, two digit
+/// years (`31.12.26`, `12/31/26`), dates without year (`31.12.`, the next such day),
+/// `today`, `tomorrow`, `yesterday`, offsets like `+3d`, `-2w`, `+1mo` or `+1y`,
+/// and weekdays like `fri` (today or later), `next fri` and `last fri`.
+pub fn parse_date(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let input = input.trim().to_lowercase();
+    match input.as_str() {
+        "today" => return Some(today),
+        "tomorrow" => return today.checked_add_days(Days::new(1)),
+        "yesterday" => return today.checked_sub_days(Days::new(1)),
+        _ => {}
+    }
+    if let Some(date) = parse_offset(&input, today) {
+        return Some(date)
+    }
+    if let Some(date) = parse_weekday_phrase(&input, today) {
+        return Some(date)
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(&input, "%Y-%m-%d") {
+        return Some(date)
+    }
+    if let Some((month, rest)) = input.split_once('/') {
+        let (day, year) = rest.split_once('/')?;
+        return from_parts(year, month, day)
+    }
+    let parts: Vec<&str> = input.split('.').collect();
+    match parts.as_slice() {
+        [day, month, year] if !year.is_empty() => from_parts(year, month, day),
+        // "31.12." and "31.12" are the next 31st of December
+        [day, month, ""] | [day, month] => {
+            let date = NaiveDate::from_ymd_opt(today.year(), month.parse().ok()?, day.parse().ok()?)?;
+            if date < today {
+                return date.checked_add_months(Months::new(12))
+            }
+            Some(date)
+        }
+        _ => None,
+    }
+}
+
+fn from_parts(year: &str, month: &str, day: &str) -> Option<NaiveDate> {
+    let year = match (year.len(), year.parse::<i32>().ok()?) {
+        (2, year) => 2000 + year,
+        (4, year) => year,
+        _ => return None,
+    };
+    NaiveDate::from_ymd_opt(year, month.parse().ok()?, day.parse().ok()?)
+}
+
+/// `+3d`, `-2w`, `+1mo` or `+1y` from today
+///
+/// Months are `mo`, as a lone `m` stands for minutes in amounts like the `for 15m` of a quick add.
+fn parse_offset(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let (forward, rest) = match (input.strip_prefix('+'), input.strip_prefix('-')) {
+        (Some(rest), _) => (true, rest),
+        (_, Some(rest)) => (false, rest),
+        _ => return None,
+    };
+    let (amount, unit) = rest.split_at(rest.find(|c: char| !c.is_ascii_digit())?);
+    let amount: u32 = amount.parse().ok()?;
+    let (days, months) = match unit {
+        "d" => (amount as u64, 0),
+        "w" => (amount as u64 * 7, 0),
+        "mo" => (0, amount),
+        "y" => (0, amount.checked_mul(12)?),
+        _ => return None,
+    };
+    if forward {
+        today.checked_add_days(Days::new(days))?.checked_add_months(Months::new(months))
+    } else {
+        today.checked_sub_days(Days::new(days))?.checked_sub_months(Months::new(months))
+    }
+}
+
+fn parse_weekday_phrase(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let words: Vec<&str> = input.split_whitespace().collect();
+    match words.as_slice() {
+        [weekday] => Some(next_weekday(today, parse_weekday(weekday)?)),
+        ["next", weekday] => Some(next_weekday(today.checked_add_days(Days::new(1))?, parse_weekday(weekday)?)),
+        ["last", weekday] => {
+            let weekday = parse_weekday(weekday)?;
+            let days = (7 + today.weekday().num_days_from_monday() - weekday.num_days_from_monday() - 1) % 7 + 1;
+            today.checked_sub_days(Days::new(days as u64))
+        }
+        _ => None,
+    }
+}
+
+/// An English weekday name or its first three letters
+pub fn parse_weekday(word: &str) -> Option<Weekday> {
+    let weekday = match word {
+        "monday" | "mon" => Weekday::Mon,
+        "tuesday" | "tue" => Weekday::Tue,
+        "wednesday" | "wed" => Weekday::Wed,
+        "thursday" | "thu" => Weekday::Thu,
+        "friday" | "fri" => Weekday::Fri,
+        "saturday" | "sat" => Weekday::Sat,
+        "sunday" | "sun" => Weekday::Sun,
+        _ => return None,
+    };
+    Some(weekday)
+}
+
+/// The first `weekday` on or after `from`
+pub fn next_weekday(from: NaiveDate, weekday: Weekday) -> NaiveDate {
+    let days = (7 + weekday.num_days_from_monday() - from.weekday().num_days_from_monday()) % 7;
+    from + Days::new(days as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sunday, 18.10.2026
+    fn today() -> NaiveDate {
+        day(18, 10, 2026)
+    }
+
+    fn day(day: u32, month: u32, year: i32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn parse(input: &str) -> Option<NaiveDate> {
+        parse_date(input, today())
+    }
+
+    #[test]
+    fn parses_written_dates() {
+        assert_eq!(parse("2026-12-31"), Some(day(31, 12, 2026)));
+        assert_eq!(parse("12/31/2026"), Some(day(31, 12, 2026)));
+        assert_eq!(parse("12/31/26"), Some(day(31, 12, 2026)));
+        assert_eq!(parse("31.12.2026"), Some(day(31, 12, 2026)));
+        assert_eq!(parse("1.2.2027"), Some(day(1, 2, 2027)));
+        assert_eq!(parse("31.12.26"), Some(day(31, 12, 2026)));
+        assert_eq!(parse(" 24.12. "), Some(day(24, 12, 2026)));
+        assert_eq!(parse("1.2"), Some(day(1, 2, 2027)));
+    }
+
+    #[test]
+    fn rejects_invalid_dates() {
+        assert_eq!(parse("31.2.2026"), None);
+        assert_eq!(parse("13/1/2026"), None);
+        assert_eq!(parse("1.1.202"), None);
+        assert_eq!(parse("someday"), None);
+        assert_eq!(parse("+3x"), None);
+        assert_eq!(parse("+d"), None);
+        assert_eq!(parse("+400000000y"), None);
+        assert_eq!(parse("-4000000000w"), None);
+        assert_eq!(parse(""), None);
+    }
+
+    #[test]
+    fn parses_relative_dates() {
+        assert_eq!(parse("today"), Some(today()));
+        assert_eq!(parse("Tomorrow"), Some(day(19, 10, 2026)));
+        assert_eq!(parse("yesterday"), Some(day(17, 10, 2026)));
+        assert_eq!(parse("+3d"), Some(day(21, 10, 2026)));
+        assert_eq!(parse("-2w"), Some(day(4, 10, 2026)));
+        assert_eq!(parse("+1mo"), Some(day(18, 11, 2026)));
+        assert_eq!(parse("+1m"), None);
+        assert_eq!(parse("+1y"), Some(day(18, 10, 2027)));
+        assert_eq!(parse("fri"), Some(day(23, 10, 2026)));
+        assert_eq!(parse("sunday"), Some(today()));
+        assert_eq!(parse("next sun"), Some(day(25, 10, 2026)));
+        assert_eq!(parse("next fri"), Some(day(23, 10, 2026)));
+        assert_eq!(parse("last sun"), Some(day(11, 10, 2026)));
+        assert_eq!(parse("last sat"), Some(day(17, 10, 2026)));
+    }
+
+    #[test]
+    fn formats_in_every_format_and_reads_it_back() {
+        let date = day(5, 3, 2026);
+        let written: Vec<String> = DateFormat::ALL.iter()
+            .map(|format| date.format(format.pattern()).to_string())
+            .collect();
+        assert_eq!(written, vec!["5.3.2026", "3/5/2026", "2026-03-05"]);
+        for text in written {
+            assert_eq!(parse(&text), Some(date));
+        }
+    }
+}
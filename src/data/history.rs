@@ -0,0 +1,276 @@
+use super::{Appointment, AppointmentKey, Task};
+
+/// Number of changes that can be undone
+pub const LIMIT: usize = 100;
+
+/// An entry of the data files, told apart from the others of its kind by its key
+pub trait Entry: Clone + PartialEq {
+    type Key: Clone + Ord;
+
+    fn id(&self) -> i32;
+    fn key(&self) -> Self::Key;
+}
+
+impl Entry for Appointment {
+    /// Appointments of different calendars may have the same id
+    type Key = AppointmentKey;
+
+    fn id(&self) -> i32 {
+        self.id
+    }
+
+    fn key(&self) -> AppointmentKey {
+        Appointment::key(self)
+    }
+}
+
+impl Entry for Task {
+    type Key = i32;
+
+    fn id(&self) -> i32 {
+        self.id
+    }
+
+    fn key(&self) -> i32 {
+        self.id
+    }
+}
+
+/// A reversible change of the appointments and tasks
+///
+/// Only the entries touched by the change are stored, as they were
+/// before and after it. `None` stands for an entry that did not exist.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    pub label: String,
+    appointments: Diff<Appointment>,
+    tasks: Diff<Task>,
+}
+
+/// The states of the touched entries of one kind
+#[derive(Debug, Clone, PartialEq)]
+struct Diff<T: Entry> {
+    before: Vec<(T::Key, Option<T>)>,
+    after: Vec<(T::Key, Option<T>)>,
+}
+
+impl<T: Entry> Diff<T> {
+    fn between(before: &[T], after: &[T]) -> Diff<T> {
+        let mut keys: Vec<T::Key> = before.iter().chain(after).map(Entry::key).collect();
+        keys.sort();
+        keys.dedup();
+        let find = |entries: &[T], key: &T::Key| entries.iter().find(|x| x.key() == *key).cloned();
+        let (before, after): (Vec<_>, Vec<_>) = keys.into_iter()
+            .map(|key| ((key.clone(), find(before, &key)), (key.clone(), find(after, &key))))
+            .filter(|(before, after)| before != after)
+            .unzip();
+        Diff { before, after }
+    }
+
+    fn none() -> Diff<T> {
+        Diff { before: vec![], after: vec![] }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.before.is_empty()
+    }
+}
+
+impl Change {
+    /// The change that turned the appointments `before` into `after`, `None` if nothing changed
+    pub fn between(label: String, before: &[Appointment], after: &[Appointment]) -> Option<Change> {
+        let appointments = Diff::between(before, after);
+        if appointments.is_empty() {
+            return None
+        }
+        Some(Change { label, appointments, tasks: Diff::none() })
+    }
+
+    /// The change of both the appointments and the tasks, each given as before and after it
+    pub fn between_all(label: String, appointments: (&[Appointment], &[Appointment]), tasks: (&[Task], &[Task])) -> Option<Change> {
+        let appointments = Diff::between(appointments.0, appointments.1);
+        let tasks = Diff::between(tasks.0, tasks.1);
+        if appointments.is_empty() && tasks.is_empty() {
+            return None
+        }
+        Some(Change { label, appointments, tasks })
+    }
+
+    /// The change that turned the tasks `before` into `after`, `None` if nothing changed
+    pub fn between_tasks(label: String, before: &[Task], after: &[Task]) -> Option<Change> {
+        let tasks = Diff::between(before, after);
+        if tasks.is_empty() {
+            return None
+        }
+        Some(Change { label, appointments: Diff::none(), tasks })
+    }
+
+    fn revert(&self, appointments: &mut Vec<Appointment>, tasks: &mut Vec<Task>) {
+        apply(appointments, &self.appointments.before);
+        apply(tasks, &self.tasks.before);
+    }
+
+    fn apply(&self, appointments: &mut Vec<Appointment>, tasks: &mut Vec<Task>) {
+        apply(appointments, &self.appointments.after);
+        apply(tasks, &self.tasks.after);
+    }
+}
+
+fn apply<T: Entry>(entries: &mut Vec<T>, states: &[(T::Key, Option<T>)]) {
+    for (key, state) in states {
+        match (entries.iter().position(|x| x.key() == *key), state) {
+            (Some(index), Some(state)) => entries[index] = state.clone(),
+            (Some(index), None) => {
+                entries.remove(index);
+            }
+            (None, Some(state)) => entries.push(state.clone()),
+            (None, None) => {}
+        }
+    }
+}
+
+/// Undo and redo stacks of the changes made in this session
+#[derive(Debug, Clone, Default)]
+pub struct History {
+    undo: Vec<Change>,
+    redo: Vec<Change>,
+}
+
+impl History {
+    /// Records a change that was just made, this drops the changes that could be redone
+    pub fn push(&mut self, change: Change) {
+        self.redo.clear();
+        self.undo.push(change);
+        if self.undo.len() > LIMIT {
+            self.undo.remove(0);
+        }
+    }
+
+    /// Reverts the last change and returns its label
+    pub fn undo(&mut self, appointments: &mut Vec<Appointment>, tasks: &mut Vec<Task>) -> Option<String> {
+        let change = self.undo.pop()?;
+        change.revert(appointments, tasks);
+        let label = change.label.clone();
+        self.redo.push(change);
+        Some(label)
+    }
+
+    /// Applies the last undone change again and returns its label
+    pub fn redo(&mut self, appointments: &mut Vec<Appointment>, tasks: &mut Vec<Task>) -> Option<String> {
+        let change = self.redo.pop()?;
+        change.apply(appointments, tasks);
+        let label = change.label.clone();
+        self.undo.push(change);
+        Some(label)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn appointment(id: i32, description: &str) -> Appointment {
+        Appointment { id, description: description.to_string(), ..Appointment::default() }
+    }
+
+    /// The appointments ordered by id, entries brought back by undo or redo are added at the end
+    fn sorted(appointments: &[Appointment]) -> Vec<Appointment> {
+        let mut sorted = appointments.to_vec();
+        sorted.sort_by_key(|x| x.id);
+        sorted
+    }
+
+    /// Makes `edit` on the appointments, records it and returns the appointments before it
+    fn commit(history: &mut History, appointments: &mut Vec<Appointment>, edit: impl FnOnce(&mut Vec<Appointment>)) -> Vec<Appointment> {
+        let before = appointments.clone();
+        edit(appointments);
+        history.push(Change::between("Edit".to_string(), &before, appointments).unwrap());
+        before
+    }
+
+    #[test]
+    fn undoes_and_redoes_appointment_changes() {
+        let mut history = History::default();
+        let mut appointments = vec![appointment(0, "Dentist"), appointment(1, "Gym")];
+        let mut tasks = vec![];
+        let states = [
+            commit(&mut history, &mut appointments, |x| x.push(appointment(2, "Added"))),
+            commit(&mut history, &mut appointments, |x| x[0].description = "Edited".to_string()),
+            commit(&mut history, &mut appointments, |x| { x.remove(1); }),
+        ];
+        let after = appointments.clone();
+
+        for state in states.iter().rev() {
+            assert_eq!(history.undo(&mut appointments, &mut tasks), Some("Edit".to_string()));
+            assert_eq!(sorted(&appointments), sorted(state));
+        }
+        assert!(!history.can_undo());
+        assert_eq!(history.undo(&mut appointments, &mut tasks), None);
+
+        for _ in &states {
+            history.redo(&mut appointments, &mut tasks);
+        }
+        assert!(!history.can_redo());
+        assert_eq!(sorted(&appointments), sorted(&after));
+    }
+
+    #[test]
+    fn tells_calendars_apart() {
+        let mut history = History::default();
+        let work = |description: &str| Appointment { calendar: Some("work.yml".to_string()), ..appointment(0, description) };
+        let mut appointments = vec![appointment(0, "Private"), work("Work")];
+        let before = commit(&mut history, &mut appointments, |x| x[1] = work("Changed"));
+        assert_eq!(appointments[0].description, "Private");
+
+        history.undo(&mut appointments, &mut vec![]);
+        assert_eq!(appointments, before);
+    }
+
+    #[test]
+    fn undoes_task_changes() {
+        let mut history = History::default();
+        let mut appointments = vec![];
+        let mut tasks = vec![Task { id: 0, ..Task::default() }];
+        let before = tasks.clone();
+        tasks[0].description = "Renamed".to_string();
+        tasks.push(Task { id: 1, ..Task::default() });
+        history.push(Change::between_tasks("Task".to_string(), &before, &tasks).unwrap());
+
+        history.undo(&mut appointments, &mut tasks);
+        assert_eq!(tasks, before);
+        assert!(appointments.is_empty());
+    }
+
+    #[test]
+    fn drops_redo_and_old_changes() {
+        let mut history = History::default();
+        let mut appointments = vec![];
+        let mut tasks = vec![];
+        assert_eq!(Change::between("Nothing".to_string(), &[appointment(0, "Same")], &[appointment(0, "Same")]), None);
+
+        commit(&mut history, &mut appointments, |x| x.push(appointment(0, "First")));
+        history.undo(&mut appointments, &mut tasks);
+        assert!(history.can_redo());
+        commit(&mut history, &mut appointments, |x| x.push(appointment(0, "Second")));
+        assert!(!history.can_redo());
+
+        for id in 1..=LIMIT as i32 {
+            commit(&mut history, &mut appointments, |x| x.push(appointment(id, "More")));
+        }
+        let mut undone = 0;
+        while history.undo(&mut appointments, &mut tasks).is_some() {
+            undone += 1;
+        }
+        assert_eq!(undone, LIMIT);
+        // The oldest change fell out of the history, so its appointment stays
+        assert_eq!(appointments, vec![appointment(0, "Second")]);
+    }
+}
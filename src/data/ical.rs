@@ -0,0 +1,713 @@
+use std::fmt;
+use std::fs::{read_to_string, write};
+use std::io;
+use std::path::Path;
+
+use chrono::{Datelike, Days, Local, NaiveDate, NaiveDateTime, TimeZone, Timelike, Utc};
+
+use super::{Alert, Appointment, Override, PDate, Priority, Recurrence, Task, date, new_id};
+
+#[derive(Debug)]
+pub enum IcalError {
+    Io(io::Error),
+    /// The text does not contain a VCALENDAR
+    MissingCalendar,
+    /// A BEGIN without matching END, the name of the component
+    Unterminated(String),
+    /// A property that could not be read, the offending line
+    InvalidProperty(String),
+}
+
+impl fmt::Display for IcalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IcalError::Io(error) => write!(f, "{}", t!("error-file-access", error = error)),
+            IcalError::MissingCalendar => write!(f, "{}", t!("ical-missing-calendar")),
+            IcalError::Unterminated(name) => write!(f, "{}", t!("ical-unterminated", name = name)),
+            IcalError::InvalidProperty(line) => write!(f, "{}", t!("ical-invalid-property", line = line)),
+        }
+    }
+}
+
+impl From<io::Error> for IcalError {
+    fn from(error: io::Error) -> Self {
+        IcalError::Io(error)
+    }
+}
+
+/// The entries of an iCalendar file
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Entries {
+    /// The VEVENTs
+    pub appointments: Vec<Appointment>,
+    /// The VTODOs
+    pub tasks: Vec<Task>,
+}
+
+/// Writes the appointments and tasks to an `.ics` file
+pub fn export_file(path: &Path, appointments: &[Appointment], tasks: &[Task]) -> Result<(), IcalError> {
+    write(path, export(appointments, tasks))?;
+    Ok(())
+}
+
+/// Reads the appointments and tasks of an `.ics` file, the ids are left at 0
+pub fn import_file(path: &Path) -> Result<Entries, IcalError> {
+    import(&read_to_string(path)?)
+}
+
+/// The UID an appointment is exported with, the one it was imported with if there is one
+pub fn uid(appointment: &Appointment) -> String {
+    match (&appointment.uid, &appointment.calendar) {
+        (Some(uid), _) => uid.clone(),
+        (None, None) => format!("{}@planer", appointment.id),
+        (None, Some(calendar)) => format!("{}.{}@planer", appointment.id, calendar),
+    }
+}
+
+/// The UID a task is exported with, the one it was imported with if there is one
+pub fn task_uid(task: &Task) -> String {
+    task.uid.clone().unwrap_or_else(|| format!("task-{}@planer", task.id))
+}
+
+/// Adds imported entries to the appointments and tasks
+///
+/// An entry with the UID of an existing one replaces it, keeping its id and
+/// calendar, so importing a file again updates what the last import added.
+/// New appointments go into the main calendar. Appointments of calendars that
+/// are not `writable` are left alone, returns how many imported ones were skipped.
+pub fn merge(imported: Entries, appointments: &mut Vec<Appointment>, tasks: &mut Vec<Task>, writable: impl Fn(&Appointment) -> bool) -> usize {
+    let mut skipped = 0;
+    for appointment in imported.appointments {
+        match appointment.uid.as_ref().and_then(|imported| find_uid(appointments, imported, |x| &x.uid, uid)) {
+            Some(index) if !writable(&appointments[index]) => skipped += 1,
+            Some(index) => {
+                let existing = &appointments[index];
+                appointments[index] = Appointment { id: existing.id, calendar: existing.calendar.clone(), ..appointment };
+            }
+            None => {
+                let id = new_id(appointments.iter().filter(|x| x.calendar.is_none()));
+                appointments.push(Appointment { id, calendar: None, ..appointment });
+            }
+        }
+    }
+    for task in imported.tasks {
+        match task.uid.as_ref().and_then(|imported| find_uid(tasks, imported, |x| &x.uid, task_uid)) {
+            // iCalendar has no checklists, so the steps of the task are kept
+            Some(index) => tasks[index] = Task { id: tasks[index].id, checklist: tasks[index].checklist.clone(), ..task },
+            None => {
+                let id = new_id(tasks.iter());
+                tasks.push(Task { id, ..task });
+            }
+        }
+    }
+    skipped
+}
+
+/// The index of the entry with the UID `imported`
+///
+/// An entry imported with that UID goes before one that is only exported with it,
+/// as entries imported from another Planer may have the UID of one of ours.
+fn find_uid<T>(entries: &[T], imported: &str, stored: impl Fn(&T) -> &Option<String>, exported: impl Fn(&T) -> String) -> Option<usize> {
+    entries.iter().position(|x| stored(x).as_deref() == Some(imported))
+        .or_else(|| entries.iter().position(|x| stored(x).is_none() && exported(x) == imported))
+}
+
+/// Serializes the appointments as VEVENTs and the tasks as VTODOs of an iCalendar VCALENDAR
+pub fn export(appointments: &[Appointment], tasks: &[Task]) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//Planer//Planer//EN".to_string(),
+    ];
+    let stamp = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+    for appointment in appointments {
+        let uid = uid(appointment);
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}", uid));
+        lines.push(format!("DTSTAMP:{}", stamp));
+        export_event(&mut lines, appointment);
+        if let Some(recurrence) = &appointment.recurrence {
+            lines.push(format!("RRULE:{}", recurrence.to_rrule()));
+        }
+        for exception in &appointment.exceptions {
+            lines.push(date_property("EXDATE", appointment.date.with_date(exception.date()), appointment.all_day));
+        }
+        lines.push("END:VEVENT".to_string());
+        for o in &appointment.overrides {
+            lines.push("BEGIN:VEVENT".to_string());
+            lines.push(format!("UID:{}", uid));
+            lines.push(format!("DTSTAMP:{}", stamp));
+            lines.push(date_property("RECURRENCE-ID", o.occurrence, appointment.all_day));
+            export_event(&mut lines, &o.appointment);
+            lines.push("END:VEVENT".to_string());
+        }
+    }
+    for task in tasks {
+        lines.push("BEGIN:VTODO".to_string());
+        lines.push(format!("UID:{}", task_uid(task)));
+        lines.push(format!("DTSTAMP:{}", stamp));
+        if let Some(due) = task.due {
+            lines.push(date_property("DUE", due, true));
+        }
+        export_text(&mut lines, &task.description);
+        lines.push(format!("PRIORITY:{}", priority_value(task.priority)));
+        match task.completed {
+            Some(completed) => {
+                lines.push("STATUS:COMPLETED".to_string());
+                lines.push(utc_property("COMPLETED", completed));
+            }
+            None => lines.push("STATUS:NEEDS-ACTION".to_string()),
+        }
+        lines.push("END:VTODO".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+    lines.iter().map(|line| fold(line)).collect::<Vec<String>>().join("\r\n") + "\r\n"
+}
+
+/// The properties describing a single appointment, without identity and recurrence
+fn export_event(lines: &mut Vec<String>, appointment: &Appointment) {
+    lines.push(date_property("DTSTART", appointment.date, appointment.all_day));
+    if appointment.all_day {
+        let end = appointment.date.date().checked_add_days(Days::new(1)).unwrap_or(appointment.date.date());
+        lines.push(date_property("DTEND", appointment.date.with_date(end), true));
+    } else {
+        lines.push(date_property("DTEND", appointment.end, false));
+    }
+    export_text(lines, &appointment.description);
+    if let Some(tags) = appointment.tags.as_ref().filter(|tags| !tags.is_empty()) {
+        let tags: Vec<String> = tags.iter().map(|tag| escape(tag)).collect();
+        lines.push(format!("CATEGORIES:{}", tags.join(",")));
+    }
+    lines.push(format!("PRIORITY:{}", priority_value(appointment.priority)));
+    let summary = appointment.description.lines().next().unwrap_or_default();
+    for alert in &appointment.alerts {
+        lines.push("BEGIN:VALARM".to_string());
+        lines.push("ACTION:DISPLAY".to_string());
+        lines.push(format!("DESCRIPTION:{}", escape(summary)));
+        match alert {
+            // RFC 5545 requires absolute triggers in UTC
+            Alert::Absolute(date) => lines.push(utc_property("TRIGGER;VALUE=DATE-TIME", *date)),
+            Alert::Relative(offset) => lines.push(format!("TRIGGER:{}", date::format_iso_duration(*offset))),
+        }
+        lines.push("END:VALARM".to_string());
+    }
+}
+
+/// The first line of the description as SUMMARY and all of it as DESCRIPTION
+fn export_text(lines: &mut Vec<String>, description: &str) {
+    let summary = description.lines().next().unwrap_or_default();
+    lines.push(format!("SUMMARY:{}", escape(summary)));
+    lines.push(format!("DESCRIPTION:{}", escape(description)));
+}
+
+fn priority_value(priority: Priority) -> u32 {
+    match priority {
+        Priority::High => 1,
+        Priority::Middle => 5,
+        Priority::Low => 9,
+    }
+}
+
+fn date_property(name: &str, date: PDate, all_day: bool) -> String {
+    if all_day {
+        format!("{};VALUE=DATE:{:04}{:02}{:02}", name, date.year, date.month, date.day)
+    } else {
+        format!("{}:{:04}{:02}{:02}T{:02}{:02}{:02}", name, date.year, date.month, date.day, date.hour, date.min, date.sec)
+    }
+}
+
+/// A local time as a time in UTC, which some properties like COMPLETED and absolute TRIGGERs require
+fn utc_property(name: &str, date: PDate) -> String {
+    let local = date::p_date_to_naive_date_time(date);
+    let utc = Local.from_local_datetime(&local).earliest().map(|time| time.naive_utc()).unwrap_or(local);
+    format!("{}:{}", name, utc.format("%Y%m%dT%H%M%SZ"))
+}
+
+/// Parses the VEVENTs of an iCalendar text into appointments and its VTODOs into tasks
+///
+/// Events sharing the UID of a recurring event and carrying a RECURRENCE-ID
+/// become overrides of that event. The ids of the entries are left at 0, their
+/// UIDs are kept to recognize them when the file is imported again.
+pub fn import(text: &str) -> Result<Entries, IcalError> {
+    let properties = unfold(text)
+        .iter()
+        .map(|line| Property::parse(line))
+        .collect::<Result<Vec<Property>, IcalError>>()?;
+    if !properties.iter().any(|p| p.name == "BEGIN" && p.value.eq_ignore_ascii_case("VCALENDAR")) {
+        return Err(IcalError::MissingCalendar)
+    }
+
+    let mut components: Vec<Component> = vec![];
+    let mut current: Option<(String, Vec<Property>, Vec<Vec<Property>>)> = None;
+    let mut in_alarm = false;
+    for property in properties {
+        let value = property.value.to_uppercase();
+        match (property.name.as_str(), value.as_str(), &mut current) {
+            ("BEGIN", "VEVENT" | "VTODO", None) => current = Some((value, vec![], vec![])),
+            ("BEGIN", "VALARM", Some((_, _, alarms))) => {
+                alarms.push(vec![]);
+                in_alarm = true;
+            }
+            ("END", "VALARM", Some(_)) => in_alarm = false,
+            ("END", name, Some((kind, _, _))) if name == kind => {
+                let (kind, event, alarms) = current.take().unwrap();
+                components.push(Component::parse(&kind, &event, &alarms)?);
+            }
+            (_, _, Some((_, event, alarms))) => {
+                if let (true, Some(alarm)) = (in_alarm, alarms.last_mut()) {
+                    alarm.push(property)
+                } else {
+                    event.push(property)
+                }
+            }
+            _ => {}
+        }
+    }
+    if let Some((kind, _, _)) = current {
+        return Err(IcalError::Unterminated(kind))
+    }
+
+    let mut entries = Entries::default();
+    let mut overrides = vec![];
+    for component in components {
+        match (component.item, component.recurrence_id) {
+            (Item::Todo(task), _) => entries.tasks.push(Task { uid: component.uid, ..task }),
+            (Item::Event(appointment), Some(occurrence)) => overrides.push((component.uid, Override { occurrence, appointment })),
+            (Item::Event(appointment), None) => entries.appointments.push(Appointment { uid: component.uid, ..appointment }),
+        }
+    }
+    for (uid, o) in overrides {
+        let series = entries.appointments.iter_mut()
+            .find(|series| series.uid == uid && series.recurrence.is_some());
+        match series {
+            Some(series) => series.overrides.push(o),
+            None => entries.appointments.push(Appointment { uid, ..o.appointment }),
+        }
+    }
+    Ok(entries)
+}
+
+struct Component {
+    uid: Option<String>,
+    recurrence_id: Option<PDate>,
+    item: Item,
+}
+
+enum Item {
+    Event(Appointment),
+    Todo(Task),
+}
+
+impl Component {
+    fn parse(kind: &str, properties: &[Property], alarms: &[Vec<Property>]) -> Result<Component, IcalError> {
+        let find = |name: &str| properties.iter().find(|p| p.name == name);
+        let recurrence_id = match find("RECURRENCE-ID") {
+            Some(id) => Some(id.date()?.0),
+            None => None,
+        };
+        let item = match kind {
+            "VTODO" => Item::Todo(Component::parse_todo(properties)?),
+            _ => Item::Event(Component::parse_event(properties, alarms)?),
+        };
+        Ok(Component { uid: find("UID").map(|p| p.value.clone()), recurrence_id, item })
+    }
+
+    fn parse_event(properties: &[Property], alarms: &[Vec<Property>]) -> Result<Appointment, IcalError> {
+        let find = |name: &str| properties.iter().find(|p| p.name == name);
+        let (date, all_day) = match find("DTSTART") {
+            Some(start) => start.date()?,
+            None => (date::naive_date_time_to_p_date(date::now()), true),
+        };
+        let end = match (find("DTEND"), find("DURATION")) {
+            _ if all_day => date,
+            (Some(end), _) => end.date()?.0,
+            (None, Some(duration)) => date::parse_iso_duration(&duration.value)
+                .and_then(|duration| date::p_date_to_naive_date_time(date).checked_add_signed(duration))
+                .map(date::naive_date_time_to_p_date)
+                .ok_or(IcalError::InvalidProperty(duration.value.clone()))?,
+            (None, None) => date,
+        };
+        let mut alerts = vec![];
+        for trigger in alarms.iter().filter_map(|alarm| alarm.iter().find(|p| p.name == "TRIGGER")) {
+            alerts.push(match trigger.value.contains('P') {
+                true => Alert::Relative(date::parse_iso_duration(&trigger.value)
+                    .ok_or(IcalError::InvalidProperty(trigger.value.clone()))?),
+                false => Alert::Absolute(trigger.date()?.0),
+            });
+        }
+        let tags: Vec<String> = properties.iter()
+            .filter(|p| p.name == "CATEGORIES")
+            .flat_map(|p| split_list(&p.value))
+            .filter(|tag| !tag.is_empty())
+            .collect();
+        let recurrence = match find("RRULE") {
+            Some(rule) => Some(Recurrence::from_rrule(&rule.value).map_err(IcalError::InvalidProperty)?),
+            None => None,
+        };
+        let mut exceptions = vec![];
+        for exdate in properties.iter().filter(|p| p.name == "EXDATE") {
+            for value in exdate.value.split(',') {
+                exceptions.push(parse_date(value, &exdate.params)?.0);
+            }
+        }
+        Ok(Appointment {
+            id: 0,
+            date,
+            end,
+            all_day,
+            priority: parse_priority(properties),
+            alerts,
+            tags: Some(tags),
+            description: parse_text(properties),
+            recurrence,
+            exceptions,
+            overrides: vec![],
+            calendar: None,
+            uid: None,
+        })
+    }
+
+    fn parse_todo(properties: &[Property]) -> Result<Task, IcalError> {
+        let find = |name: &str| properties.iter().find(|p| p.name == name);
+        // Tasks are due on a day, the time is dropped
+        let due = match find("DUE").or(find("DTSTART")) {
+            Some(due) => Some(due.date()?.0.date()),
+            None => None,
+        };
+        let completed = match find("COMPLETED") {
+            Some(completed) => Some(completed.date()?.0),
+            None if find("STATUS").is_some_and(|p| p.value.eq_ignore_ascii_case("COMPLETED")) => Some(PDate::now()),
+            None => None,
+        };
+        Ok(Task {
+            description: parse_text(properties),
+            priority: parse_priority(properties),
+            due: due.map(|due| PDate::new(due.year(), due.month(), due.day(), 0, 0, 0)),
+            completed,
+            ..Task::default()
+        })
+    }
+}
+
+/// The description from SUMMARY and DESCRIPTION, joined unless one contains the other
+fn parse_text(properties: &[Property]) -> String {
+    let find = |name: &str| properties.iter().find(|p| p.name == name);
+    let summary = find("SUMMARY").map(|p| unescape(&p.value)).unwrap_or_default();
+    match find("DESCRIPTION").map(|p| unescape(&p.value)) {
+        Some(description) if description.starts_with(&summary) => description,
+        Some(description) if summary.is_empty() => description,
+        Some(description) => format!("{}\n{}", summary, description),
+        None => summary,
+    }
+}
+
+fn parse_priority(properties: &[Property]) -> Priority {
+    match properties.iter().find(|p| p.name == "PRIORITY").and_then(|p| p.value.trim().parse::<u32>().ok()) {
+        Some(1..=4) => Priority::High,
+        Some(5) => Priority::Middle,
+        _ => Priority::Low,
+    }
+}
+
+struct Property {
+    name: String,
+    params: Vec<(String, String)>,
+    value: String,
+}
+
+impl Property {
+    fn parse(line: &str) -> Result<Property, IcalError> {
+        let mut in_quotes = false;
+        let split = line.char_indices().find(|(_, c)| {
+            if *c == '"' {
+                in_quotes = !in_quotes;
+            }
+            *c == ':' && !in_quotes
+        });
+        let Some((index, _)) = split else {
+            return Err(IcalError::InvalidProperty(line.to_string()))
+        };
+        let (head, value) = (&line[..index], &line[index + 1..]);
+        let mut parts = head.split(';');
+        let name = parts.next().unwrap_or_default().trim().to_uppercase();
+        let params = parts
+            .filter_map(|param| param.split_once('='))
+            .map(|(key, value)| (key.to_uppercase(), value.trim_matches('"').to_string()))
+            .collect();
+        Ok(Property { name, params, value: value.to_string() })
+    }
+
+    /// The value as a date, and whether it is a DATE without time
+    fn date(&self) -> Result<(PDate, bool), IcalError> {
+        parse_date(&self.value, &self.params)
+    }
+}
+
+fn parse_date(value: &str, params: &[(String, String)]) -> Result<(PDate, bool), IcalError> {
+    let value = value.trim();
+    let is_date = params.iter().any(|(key, value)| key == "VALUE" && value.eq_ignore_ascii_case("DATE"));
+    if is_date || value.len() == 8 {
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d")
+            .map_err(|_| IcalError::InvalidProperty(value.to_string()))?;
+        return Ok((PDate::new(date.year(), date.month(), date.day(), 0, 0, 0), true))
+    }
+    let time = NaiveDateTime::parse_from_str(value.trim_end_matches('Z'), "%Y%m%dT%H%M%S")
+        .map_err(|_| IcalError::InvalidProperty(value.to_string()))?;
+    // Times in UTC are shown in local time, times with a TZID are taken as they are
+    let time = match value.ends_with('Z') {
+        true => Utc.from_utc_datetime(&time).with_timezone(&Local).naive_local(),
+        false => time,
+    };
+    Ok((PDate::new(time.year(), time.month(), time.day(), time.hour(), time.minute(), time.second()), false))
+}
+
+/// Joins continuation lines, which start with a space or tab, to their property
+fn unfold(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = vec![];
+    for line in text.lines() {
+        match (line.strip_prefix(' ').or(line.strip_prefix('\t')), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ if line.trim().is_empty() => {}
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+/// Splits lines longer than 75 octets into continuation lines
+fn fold(line: &str) -> String {
+    let mut folded = String::new();
+    let mut length = 0;
+    for c in line.chars() {
+        if length + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            length = 1;
+        }
+        folded.push(c);
+        length += c.len_utf8();
+    }
+    folded
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+fn unescape(text: &str) -> String {
+    let mut unescaped = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match (c, c == '\\') {
+            (_, true) => match chars.next() {
+                Some('n') | Some('N') => unescaped.push('\n'),
+                Some(next) => unescaped.push(next),
+                None => {}
+            },
+            (c, false) => unescaped.push(c),
+        }
+    }
+    unescaped
+}
+
+/// Splits a comma separated list value, keeping escaped commas
+fn split_list(value: &str) -> Vec<String> {
+    let mut items = vec![];
+    let mut item = String::new();
+    let mut escaped = false;
+    for c in value.chars() {
+        match c {
+            ',' if !escaped => items.push(unescape(&std::mem::take(&mut item))),
+            _ => item.push(c),
+        }
+        escaped = c == '\\' && !escaped;
+    }
+    items.push(unescape(&item));
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{AppointmentKey, ByDay, Frequency};
+    use chrono::Duration;
+
+    fn round_trip(entries: &Entries) -> Entries {
+        import(&export(&entries.appointments, &entries.tasks)).unwrap()
+    }
+
+    fn appointments_only(appointments: Vec<Appointment>) -> Entries {
+        Entries { appointments, tasks: vec![] }
+    }
+
+    #[test]
+    fn imports_sample_event() {
+        let appointments = import(include_str!("../../tests/samples/event.ics")).unwrap().appointments;
+        assert_eq!(appointments.len(), 2);
+
+        let meeting = &appointments[0];
+        assert_eq!(meeting.date, PDate::new(2026, 10, 20, 14, 0, 0));
+        assert_eq!(meeting.end, PDate::new(2026, 10, 20, 15, 30, 0));
+        assert!(!meeting.all_day);
+        assert_eq!(meeting.description, "Team meeting\nDiscuss the roadmap, budget; and hiring");
+        assert_eq!(meeting.tags, Some(vec!["work".to_string(), "planning".to_string()]));
+        assert_eq!(meeting.priority, Priority::High);
+        assert_eq!(meeting.alerts, vec![Alert::Relative(Duration::minutes(-15))]);
+
+        let holiday = &appointments[1];
+        assert!(holiday.all_day);
+        assert_eq!(holiday.date.date(), NaiveDate::from_ymd_opt(2026, 12, 25).unwrap());
+        assert_eq!(holiday.priority, Priority::Middle);
+        assert_eq!(holiday.alerts, vec![Alert::Absolute(PDate::new(2026, 12, 24, 0, 0, 0))]);
+    }
+
+    #[test]
+    fn imports_sample_recurring_event() {
+        let appointments = import(include_str!("../../tests/samples/recurring.ics")).unwrap().appointments;
+        assert_eq!(appointments.len(), 1);
+
+        let series = &appointments[0];
+        let recurrence = series.recurrence.as_ref().unwrap();
+        assert_eq!(recurrence.frequency, Frequency::Weekly);
+        assert_eq!(recurrence.interval, 2);
+        assert_eq!(recurrence.by_day, vec![ByDay::try_from("MO".to_string()).unwrap()]);
+        assert_eq!(recurrence.count, Some(10));
+        assert_eq!(series.exceptions, vec![PDate::new(2026, 11, 9, 10, 0, 0)]);
+        assert_eq!(series.overrides.len(), 1);
+        assert_eq!(series.overrides[0].occurrence, PDate::new(2026, 11, 23, 10, 0, 0));
+        assert_eq!(series.overrides[0].appointment.date, PDate::new(2026, 11, 24, 11, 0, 0));
+    }
+
+    #[test]
+    fn imports_sample_todo() {
+        let entries = import(include_str!("../../tests/samples/todo.ics")).unwrap();
+        assert!(entries.appointments.is_empty());
+        assert_eq!(entries.tasks, vec![Task {
+            description: "File taxes".to_string(),
+            due: Some(PDate::new(2026, 10, 31, 0, 0, 0)),
+            uid: Some("taxes@example.com".to_string()),
+            ..Task::default()
+        }]);
+    }
+
+    #[test]
+    fn exports_absolute_alerts_in_utc() {
+        let appointment = Appointment { alerts: vec![Alert::Absolute(PDate::new(2026, 12, 24, 8, 30, 0))], ..Appointment::default() };
+        let text = export(std::slice::from_ref(&appointment), &[]);
+        let trigger = text.lines().find(|line| line.starts_with("TRIGGER;VALUE=DATE-TIME:")).unwrap();
+        assert!(trigger.ends_with('Z'), "{}", trigger);
+        assert_eq!(round_trip(&appointments_only(vec![appointment.clone()])).appointments[0].alerts, appointment.alerts);
+    }
+
+    #[test]
+    fn tasks_round_trip() {
+        let tasks = Entries { appointments: vec![], tasks: vec![
+            Task { id: 3, description: "Open".to_string(), priority: Priority::High, ..Task::default() },
+            Task {
+                id: 4,
+                description: "Done\nlong ago".to_string(),
+                due: Some(PDate::new(2026, 9, 1, 0, 0, 0)),
+                completed: Some(PDate::new(2026, 8, 30, 17, 45, 0)),
+                ..Task::default()
+            },
+        ] };
+        let text = export(&[], &tasks.tasks);
+        assert_eq!(text.matches("BEGIN:VTODO").count(), 2);
+        assert!(!text.contains("BEGIN:VEVENT"));
+
+        let imported = round_trip(&tasks);
+        assert_eq!(imported.tasks[0].uid.as_deref(), Some("task-3@planer"));
+        let imported: Vec<Task> = imported.tasks.into_iter().map(|task| Task { uid: None, ..task }).collect();
+        let expected: Vec<Task> = tasks.tasks.into_iter().map(|task| Task { id: 0, ..task }).collect();
+        assert_eq!(imported, expected);
+    }
+
+    #[test]
+    fn importing_again_updates_instead_of_duplicating() {
+        let series = Appointment {
+            id: 1,
+            recurrence: Some(Recurrence::from_rrule("FREQ=DAILY;COUNT=3").unwrap()),
+            ..Appointment::default()
+        };
+        let work = Appointment { id: 0, calendar: Some("work.yml".to_string()), description: "Work".to_string(), ..Appointment::default() };
+        let mut appointments = vec![Appointment { description: "Dentist".to_string(), ..Appointment::default() }, series, work];
+        let mut tasks = vec![Task { checklist: vec![Default::default()], ..Task::default() }];
+        let original = appointments.clone();
+
+        let mut exported = import(&export(&appointments, &tasks)).unwrap();
+        exported.appointments[0].description = "Dentist at 9".to_string();
+        exported.tasks[0].description = "Renamed".to_string();
+        merge(exported.clone(), &mut appointments, &mut tasks, |_| true);
+        merge(exported, &mut appointments, &mut tasks, |_| true);
+        assert_eq!(appointments.len(), 3);
+        assert_eq!(appointments[0].description, "Dentist at 9");
+        assert_eq!(appointments.iter().map(Appointment::key).collect::<Vec<_>>(), original.iter().map(Appointment::key).collect::<Vec<_>>());
+        assert_eq!(tasks.len(), 1);
+        assert_eq!((tasks[0].description.as_str(), tasks[0].checklist.len()), ("Renamed", 1));
+
+        let foreign = Appointment { uid: Some("meeting@example.com".to_string()), ..Appointment::default() };
+        merge(appointments_only(vec![foreign.clone()]), &mut appointments, &mut tasks, |_| true);
+        merge(appointments_only(vec![foreign]), &mut appointments, &mut tasks, |_| true);
+        assert_eq!(appointments.len(), 4);
+        assert_eq!(appointments[3].key(), AppointmentKey { calendar: None, id: 2 });
+    }
+
+    #[test]
+    fn prefers_imported_uids() {
+        // An appointment imported from another Planer with the UID our appointment 0 is exported with
+        let mut appointments = vec![
+            Appointment { description: "Ours".to_string(), ..Appointment::default() },
+            Appointment { id: 1, description: "Theirs".to_string(), uid: Some("0@planer".to_string()), ..Appointment::default() },
+        ];
+        let changed = Appointment { description: "Theirs, changed".to_string(), uid: Some("0@planer".to_string()), ..Appointment::default() };
+        merge(appointments_only(vec![changed]), &mut appointments, &mut vec![], |_| true);
+        assert_eq!(appointments[0].description, "Ours");
+        assert_eq!(appointments[1].description, "Theirs, changed");
+    }
+
+    #[test]
+    fn skips_appointments_of_read_only_calendars() {
+        let holiday = Appointment { calendar: Some("holidays.yml".to_string()), description: "Holiday".to_string(), ..Appointment::default() };
+        let mut appointments = vec![holiday.clone()];
+        let changed = Appointment { description: "Changed".to_string(), uid: Some(uid(&holiday)), ..holiday.clone() };
+        let new = Appointment { uid: Some("new@example.com".to_string()), ..Appointment::default() };
+        let skipped = merge(appointments_only(vec![changed, new]), &mut appointments, &mut vec![], |x| x.calendar.is_none());
+        assert_eq!(skipped, 1);
+        assert_eq!(appointments.len(), 2);
+        assert_eq!(appointments[0], holiday);
+    }
+
+    #[test]
+    fn sample_files_round_trip() {
+        for sample in [
+            include_str!("../../tests/samples/event.ics"),
+            include_str!("../../tests/samples/recurring.ics"),
+            include_str!("../../tests/samples/todo.ics"),
+        ] {
+            let entries = import(sample).unwrap();
+            assert_eq!(round_trip(&entries), entries);
+        }
+    }
+
+    #[test]
+    fn long_and_special_text_round_trips() {
+        let appointments = vec![Appointment {
+            description: "A very long description, with commas; semicolons \\ backslashes and\nnewlines that needs folding äöü".repeat(3),
+            tags: Some(vec!["a,b".to_string(), "c".to_string()]),
+            uid: Some("long@example.com".to_string()),
+            ..Appointment::default()
+        }];
+        let text = export(&appointments, &[]);
+        assert!(text.lines().all(|line| line.len() <= 75));
+        let entries = appointments_only(appointments);
+        assert_eq!(round_trip(&entries), entries);
+    }
+
+    #[test]
+    fn rejects_invalid_input() {
+        assert!(matches!(import("BEGIN:VEVENT\r\nEND:VEVENT"), Err(IcalError::MissingCalendar)));
+        assert!(matches!(import("BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\n"), Err(IcalError::Unterminated(_))));
+    }
+}
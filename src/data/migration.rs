@@ -0,0 +1,128 @@
+use serde_yaml::{Mapping, Value};
+
+use super::storage::StorageError;
+use super::PDate;
+
+/// Version of the data file written by this build of Planer
+pub const VERSION: u32 = 3;
+
+/// Upgrades a data file by one version, the migration at index `i` turns version `i` into `i + 1`
+type Migration = fn(Value) -> Result<Value, String>;
+
+const MIGRATIONS: &[Migration] = &[v0_to_v1, v1_to_v2, v2_to_v3];
+
+/// The version of a data file, files without envelope are version 0
+pub fn version_of(value: &Value) -> Result<u32, StorageError> {
+    match value {
+        Value::Sequence(_) => Ok(0),
+        Value::Mapping(mapping) => mapping.get("version")
+            .and_then(Value::as_u64)
+            .map(|version| version as u32)
+            .ok_or(StorageError::Migration("the file has no version".to_string())),
+        _ => Err(StorageError::Migration("the file is neither a list nor a mapping".to_string())),
+    }
+}
+
+/// Runs all migrations needed to bring `value` from its version to [`VERSION`]
+pub fn migrate(mut value: Value) -> Result<Value, StorageError> {
+    let version = version_of(&value)?;
+    if version > VERSION {
+        return Err(StorageError::UnsupportedVersion(version))
+    }
+    for (from, migration) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+        value = migration(value)
+            .map_err(|error| StorageError::Migration(format!("version {} to {}: {}", from, from + 1, error)))?;
+    }
+    Ok(value)
+}
+
+/// Wraps the bare list of appointments into the versioned envelope
+fn v0_to_v1(value: Value) -> Result<Value, String> {
+    let mut mapping = Mapping::new();
+    mapping.insert("version".into(), 1.into());
+    mapping.insert("metadata".into(), Value::Mapping(Mapping::new()));
+    mapping.insert("appointments".into(), value);
+    Ok(Value::Mapping(mapping))
+}
+
+/// Replaces the single `warning` date of every appointment by a list of alerts
+///
+/// Warnings still at the default of 1.1.1970 were never set and are dropped.
+fn v1_to_v2(mut value: Value) -> Result<Value, String> {
+    let appointments = value.get_mut("appointments")
+        .and_then(Value::as_sequence_mut)
+        .ok_or("the file has no list of appointments")?;
+    for appointment in appointments {
+        warning_to_alerts(appointment)?;
+    }
+    value["version"] = 2.into();
+    Ok(value)
+}
+
+/// Version 3 added the reminder states and the tasks next to the appointments
+///
+/// Older files have neither, the version only changes so builds that would drop them refuse the file.
+fn v2_to_v3(mut value: Value) -> Result<Value, String> {
+    value["version"] = 3.into();
+    Ok(value)
+}
+
+fn warning_to_alerts(appointment: &mut Value) -> Result<(), String> {
+    let appointment = appointment.as_mapping_mut().ok_or("an appointment is not a mapping")?;
+    let alerts = match appointment.remove("warning") {
+        Some(warning) if warning != serde_yaml::to_value(PDate::default()).map_err(|e| e.to_string())? => vec![warning],
+        _ => vec![],
+    };
+    appointment.insert("alerts".into(), Value::Sequence(alerts));
+    if let Some(overrides) = appointment.get_mut("overrides").and_then(Value::as_sequence_mut) {
+        for o in overrides {
+            if let Some(appointment) = o.get_mut("appointment") {
+                warning_to_alerts(appointment)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::storage::SavedData;
+    use crate::data::Alert;
+
+    #[test]
+    fn migrates_bare_list() {
+        let old = "- id: 3\n  date: {year: 2026, month: 10, day: 18, hour: 0, min: 0, sec: 0}\n  description: Old\n";
+        let value = migrate(serde_yaml::from_str(old).unwrap()).unwrap();
+        let data: SavedData = serde_yaml::from_value(value).unwrap();
+        assert_eq!(data.version, VERSION);
+        assert_eq!(data.appointments.len(), 1);
+        assert_eq!(data.appointments[0].id, 3);
+        assert_eq!(data.appointments[0].description, "Old");
+    }
+
+    #[test]
+    fn turns_warnings_into_alerts() {
+        let old = "version: 1\nappointments:\n- id: 1\n  warning: {year: 2026, month: 10, day: 17, hour: 0, min: 0, sec: 0}\n- id: 2\n  warning: {year: 1970, month: 1, day: 1, hour: 0, min: 0, sec: 0}\n";
+        let value = migrate(serde_yaml::from_str(old).unwrap()).unwrap();
+        let data: SavedData = serde_yaml::from_value(value).unwrap();
+        assert_eq!(data.appointments[0].alerts, vec![Alert::Absolute(PDate::new(2026, 10, 17, 0, 0, 0))]);
+        assert!(data.appointments[1].alerts.is_empty());
+    }
+
+    #[test]
+    fn keeps_version_2_data() {
+        let old = "version: 2\nappointments:\n- id: 4\n  description: Kept\n";
+        let value = migrate(serde_yaml::from_str(old).unwrap()).unwrap();
+        let data: SavedData = serde_yaml::from_value(value).unwrap();
+        assert_eq!(data.version, 3);
+        assert_eq!(data.appointments[0].description, "Kept");
+        assert!(data.reminders.is_empty() && data.tasks.is_empty());
+    }
+
+    #[test]
+    fn rejects_newer_files() {
+        let value = serde_yaml::from_str("version: 999\nappointments: []\n").unwrap();
+        assert!(matches!(migrate(value), Err(StorageError::UnsupportedVersion(999))));
+    }
+}
@@ -1,11 +1,28 @@
 pub mod date;
+pub mod date_format;
+pub mod config;
+pub mod calendars;
+pub mod theme;
 pub mod common;
+pub mod recurrence;
+pub mod ical;
+pub mod storage;
+pub mod migration;
+pub mod history;
+pub mod search;
+pub mod reminder;
+pub mod quick_add;
+pub mod task;
 
 pub use date::PDate;
+pub use recurrence::{Recurrence, Frequency, ByDay};
 pub use common::file_path;
+pub use reminder::Alert;
+pub use task::Task;
+use std::collections::BTreeMap;
 use std::fmt;
 
-use std::fs::{read_to_string, OpenOptions, remove_file};
+use chrono::{Datelike, Duration, NaiveDate, NaiveTime, Timelike};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
@@ -17,32 +34,113 @@ pub enum Priority {
 
 impl Priority {
     pub const ALL: &[Self] = &[Self::High, Self::Middle, Self::Low];
+
+    /// The name used in search queries and on the command line, the same in every language
+    pub fn name(self) -> &'static str {
+        match self {
+            Priority::High => "high",
+            Priority::Middle => "middle",
+            Priority::Low => "low",
+        }
+    }
+
+    /// The priority called `name`, ignoring case
+    pub fn from_name(name: &str) -> Option<Priority> {
+        Priority::ALL.iter().copied().find(|priority| priority.name().eq_ignore_ascii_case(name))
+    }
 }
 
 impl fmt::Display for Priority {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Priority::High => "High",
-            Priority::Middle => "Middle",
-            Priority::Low => "Low",
+            Priority::High => t!("priority-high"),
+            Priority::Middle => t!("priority-middle"),
+            Priority::Low => t!("priority-low"),
         }
         .fmt(f)
     }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
 pub struct Appointment {
     pub id: i32,
+    /// Start of the appointment, the time part is ignored for all-day entries
     pub date: PDate,
+    /// End of the appointment, ignored for all-day entries
+    pub end: PDate,
+    pub all_day: bool,
     pub priority: Priority,
-    pub warning: PDate,
+    /// When to be reminded of the appointment
+    pub alerts: Vec<Alert>,
     pub tags: Option<Vec<String>>,
     pub description: String,
+    pub recurrence: Option<Recurrence>,
+    /// Occurrences removed from a recurring series
+    pub exceptions: Vec<PDate>,
+    /// Occurrences of a recurring series that were edited on their own
+    pub overrides: Vec<Override>,
+    /// File of the calendar the appointment belongs to, `None` for the main calendar
+    ///
+    /// It is not saved, as each calendar has a file of its own.
+    #[serde(skip)]
+    pub calendar: Option<String>,
+    /// UID of the event in the iCalendar file it was imported from
+    pub uid: Option<String>,
+}
+
+/// Tells appointments apart, their ids are only unique within the file of their calendar
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct AppointmentKey {
+    /// File of the calendar, `None` for the main calendar
+    pub calendar: Option<String>,
+    pub id: i32,
+}
+
+/// All tags used by the appointments with the number of appointments using them, sorted by name
+pub fn tag_counts(appointments: &[Appointment]) -> Vec<(String, usize)> {
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+    for appointment in appointments {
+        let mut tags: Vec<&String> = appointment.tag_list()
+            .chain(appointment.overrides.iter().flat_map(|o| o.appointment.tag_list()))
+            .collect();
+        tags.sort();
+        tags.dedup();
+        for tag in tags {
+            *counts.entry(tag.clone()).or_default() += 1;
+        }
+    }
+    counts.into_iter().collect()
+}
+
+/// A modified occurrence of a recurring appointment
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Override {
+    /// Date of the occurrence in the unmodified series
+    pub occurrence: PDate,
+    pub appointment: Appointment,
+}
+
+/// Which occurrences of a recurring series an edit applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditScope {
+    This,
+    Following,
+    All,
+}
+
+/// A single occurrence of an appointment on a specific day
+#[derive(Debug, Clone, PartialEq)]
+pub struct Occurrence {
+    /// The appointment as it takes place on this day, with overrides applied
+    pub appointment: Appointment,
+    /// Date of the occurrence in the unmodified series
+    pub original: PDate,
 }
 
 impl Default for Appointment {
     fn default() -> Self {
-        Appointment { id: 0, date: PDate::default(), priority: Priority::Low, warning: PDate::default(), tags: None, description: "Description".to_string() }
+        Appointment { id: 0, date: PDate::default(), end: PDate::default(), all_day: true, priority: Priority::Low, alerts: vec![], tags: None, description: "Description".to_string(), recurrence: None, exceptions: vec![], overrides: vec![], calendar: None, uid: None }
     }
 }
 
@@ -50,25 +148,403 @@ impl Appointment {
     pub fn description(&self) -> &String {
         &self.description
     }
+
+    pub fn key(&self) -> AppointmentKey {
+        AppointmentKey { calendar: self.calendar.clone(), id: self.id }
+    }
+
+    /// Start time of the appointment, `None` for all-day entries
+    pub fn start_time(&self) -> Option<NaiveTime> {
+        if self.all_day {
+            return None
+        }
+        NaiveTime::from_hms_opt(self.date.hour, self.date.min, self.date.sec)
+    }
+
+    /// End time of the appointment, `None` for all-day entries
+    pub fn end_time(&self) -> Option<NaiveTime> {
+        if self.all_day {
+            return None
+        }
+        NaiveTime::from_hms_opt(self.end.hour, self.end.min, self.end.sec)
+    }
+
+    /// Key used to order the appointments of a day, all-day entries come first
+    pub fn sort_key(&self) -> (bool, Option<NaiveTime>) {
+        (!self.all_day, self.start_time())
+    }
+
+    /// The tags of the appointment, without empty ones
+    pub fn tag_list(&self) -> impl Iterator<Item = &String> {
+        self.tags.iter().flatten().filter(|tag| !tag.is_empty())
+    }
+
+    /// Replaces the tag `from` with `to`, also in the modified occurrences of a series
+    ///
+    /// Renaming to a tag the appointment already has merges both. Returns whether anything changed.
+    pub fn rename_tag(&mut self, from: &str, to: &str) -> bool {
+        let mut changed = false;
+        if let Some(tags) = &mut self.tags {
+            if tags.iter().any(|tag| tag == from) {
+                let mut renamed: Vec<String> = vec![];
+                for tag in tags.drain(..) {
+                    let tag = if tag == from { to.to_string() } else { tag };
+                    if !renamed.contains(&tag) {
+                        renamed.push(tag);
+                    }
+                }
+                *tags = renamed;
+                changed = true;
+            }
+        }
+        for o in &mut self.overrides {
+            changed |= o.appointment.rename_tag(from, to);
+        }
+        changed
+    }
+
+    /// Start and end time formatted as `hh:mm - hh:mm`, or "All day"
+    pub fn time_string(&self) -> String {
+        match (self.start_time(), self.end_time()) {
+            (Some(start), Some(end)) => format!("{} - {}", start.format("%H:%M"), end.format("%H:%M")),
+            _ => t!("all-day"),
+        }
+    }
+
+    /// The occurrences of this appointment taking place on `day`
+    ///
+    /// Recurring series are expanded lazily, only the requested day is computed.
+    pub fn occurrences_on(&self, day: NaiveDate) -> Vec<Occurrence> {
+        let Some(recurrence) = &self.recurrence else {
+            if self.date.date() == day {
+                return vec![Occurrence { appointment: self.clone(), original: self.date }]
+            }
+            return vec![]
+        };
+        let mut occurrences: Vec<Occurrence> = self.overrides.iter()
+            .filter(|o| o.appointment.date.date() == day)
+            .map(|o| Occurrence {
+                appointment: Appointment { id: self.id, calendar: self.calendar.clone(), recurrence: None, exceptions: vec![], overrides: vec![], ..o.appointment.clone() },
+                original: o.occurrence,
+            })
+            .collect();
+        let original = self.date.with_date(day);
+        let replaced = self.exceptions.iter().chain(self.overrides.iter().map(|o| &o.occurrence))
+            .any(|date| date.date() == day);
+        if !replaced && recurrence.occurs_on(self.date.date(), day) {
+            occurrences.push(Occurrence { appointment: self.moved_to(day), original });
+        }
+        occurrences
+    }
+
+    /// The occurrence of this appointment that originally took place at `original`
+    pub fn occurrence(&self, original: PDate) -> Occurrence {
+        if self.recurrence.is_none() {
+            return Occurrence { appointment: self.clone(), original: self.date }
+        }
+        match self.overrides.iter().find(|o| o.occurrence == original) {
+            Some(o) => Occurrence {
+                appointment: Appointment { id: self.id, calendar: self.calendar.clone(), recurrence: None, exceptions: vec![], overrides: vec![], ..o.appointment.clone() },
+                original,
+            },
+            None => Occurrence { appointment: self.moved_to(original.date()), original },
+        }
+    }
+
+    /// Replaces the single occurrence at `original` with `edited`
+    pub fn override_occurrence(&mut self, original: PDate, edited: Appointment) {
+        self.overrides.retain(|o| o.occurrence != original);
+        self.overrides.push(Override {
+            occurrence: original,
+            appointment: Appointment { id: self.id, recurrence: None, exceptions: vec![], overrides: vec![], ..edited },
+        });
+    }
+
+    /// A single appointment with the content of the occurrence at `original`, the id is left as it is
+    ///
+    /// A copy of an occurrence of a series does not repeat, so it has no removed or modified
+    /// occurrences either. It is a new event, importing the original again does not replace it.
+    pub fn duplicate(&self, original: PDate) -> Appointment {
+        Appointment { recurrence: None, exceptions: vec![], overrides: vec![], uid: None, ..self.occurrence(original).appointment }
+    }
+
+    /// Removes the single occurrence at `original` from the series
+    pub fn exclude(&mut self, original: PDate) {
+        self.overrides.retain(|o| o.occurrence != original);
+        self.exceptions.push(original);
+    }
+
+    /// Applies `edited` to the whole series, moving it by as many days as the occurrence at `original` moved
+    ///
+    /// Removed and modified occurrences move along, so they keep replacing the same occurrences.
+    pub fn edit_series(&mut self, original: PDate, edited: Appointment) {
+        let offset = edited.date.date().signed_duration_since(original.date());
+        let shift = |date: NaiveDate| date.checked_add_signed(offset).unwrap_or(date);
+        let series = edited.moved_to(shift(self.date.date()));
+        let exceptions = self.exceptions.iter()
+            .map(|date| series.date.with_date(shift(date.date())))
+            .collect();
+        let overrides = self.overrides.iter()
+            .map(|o| Override {
+                occurrence: series.date.with_date(shift(o.occurrence.date())),
+                appointment: o.appointment.moved_to(shift(o.appointment.date.date())),
+            })
+            .collect();
+        *self = Appointment { id: self.id, exceptions, overrides, ..series };
+    }
+
+    /// Ends the series right before the occurrence at `original`
+    ///
+    /// Returns the number of occurrences that are left in the series.
+    pub fn truncate_before(&mut self, original: PDate) -> u32 {
+        let start = self.date.date();
+        let Some(recurrence) = &mut self.recurrence else {
+            return 1
+        };
+        let kept = recurrence.occurrences(start).take_while(|date| *date < original.date()).count() as u32;
+        recurrence.count = None;
+        recurrence.until = original.date().pred_opt().map(|date| PDate::new(date.year(), date.month(), date.day(), 0, 0, 0));
+        self.exceptions.retain(|date| date.date() < original.date());
+        self.overrides.retain(|o| o.occurrence.date() < original.date());
+        kept
+    }
+
+    /// A copy of the appointment moved to `day`, keeping the time and how long before it the alerts fire
+    pub fn moved_to(&self, day: NaiveDate) -> Appointment {
+        let offset = day.signed_duration_since(self.date.date());
+        Appointment {
+            date: self.date.with_date(day),
+            end: self.end.with_date(day),
+            alerts: self.shifted_alerts(offset),
+            ..self.clone()
+        }
+    }
+
+    /// The appointment starting at `start` on `day` with the same length, an all-day appointment gets an hour
+    ///
+    /// It ends at midnight at the latest, as appointments do not reach into the next day.
+    pub fn moved_to_time(&self, day: NaiveDate, start: NaiveTime) -> Appointment {
+        let (old_start, length) = match (self.start_time(), self.end_time()) {
+            (Some(old_start), Some(end)) => (old_start, end - old_start),
+            _ => (NaiveTime::MIN, Duration::hours(1)),
+        };
+        let offset = day.and_time(start) - self.date.date().and_time(old_start);
+        let end = (day.and_time(start) + length).min(day.and_hms_opt(23, 59, 0).unwrap()).time();
+        Appointment {
+            date: PDate { hour: start.hour(), min: start.minute(), sec: 0, ..self.date.with_date(day) },
+            end: PDate { hour: end.hour(), min: end.minute(), sec: 0, ..self.end.with_date(day) },
+            all_day: false,
+            alerts: self.shifted_alerts(offset),
+            ..self.clone()
+        }
+    }
+
+    /// The alerts for the appointment taking place `offset` later, relative ones stay as they are
+    fn shifted_alerts(&self, offset: Duration) -> Vec<Alert> {
+        self.alerts.iter()
+            .map(|alert| match alert {
+                Alert::Absolute(date) => Alert::Absolute(date::p_date_to_naive_date_time(*date)
+                    .checked_add_signed(offset)
+                    .map(date::naive_date_time_to_p_date)
+                    .unwrap_or(*date)),
+                relative => *relative,
+            })
+            .collect()
+    }
 }
 
-pub fn read_appointments() -> Vec<Appointment> {
-    let mut path = file_path();
-    path.push("saved.yml");
-    let binding = read_to_string(path).unwrap();
-    let file = binding.as_str();
-    let scrape_config: Vec<Appointment> = serde_yaml::from_str(file).ok().unwrap();
-    scrape_config
+/// The smallest id not used by any of the entries, appointments or tasks
+pub fn new_id<'a, T: history::Entry + 'a>(entries: impl IntoIterator<Item = &'a T>) -> i32 {
+    let mut ids: Vec<i32> = entries.into_iter().map(history::Entry::id).collect();
+    ids.sort();
+    for (i, id) in ids.iter().enumerate() {
+        if i as i32 != *id {
+            return i as i32
+        }
+    }
+    ids.len() as i32
 }
 
-pub fn save_appointments(appointments: Vec<Appointment>) {
-    let mut path = file_path();
-    path.push("saved.yml");
-    let _ = remove_file(&path);
-    let file = OpenOptions::new()
-        .create(true)
-        .write(true)    
-        .open(path)
-        .unwrap();
-    serde_yaml::to_writer(file, &appointments).unwrap();
-}
\ No newline at end of file
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn weekly(day: u32) -> Appointment {
+        Appointment {
+            date: PDate::new(2026, 10, day, 9, 0, 0),
+            end: PDate::new(2026, 10, day, 10, 0, 0),
+            all_day: false,
+            recurrence: Some(Recurrence::default()),
+            ..Appointment::default()
+        }
+    }
+
+    fn day(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap()
+    }
+
+    fn at_nine(day: u32) -> PDate {
+        PDate::new(2026, 10, day, 9, 0, 0)
+    }
+
+    /// A weekly series from the 5th with the occurrence of the 19th renamed to "Changed"
+    fn series_with_override() -> Appointment {
+        let mut series = weekly(5);
+        let changed = Appointment { description: "Changed".to_string(), ..series.occurrence(at_nine(19)).appointment };
+        series.override_occurrence(at_nine(19), changed);
+        series
+    }
+
+    #[test]
+    fn duplicates_occurrences_as_single_appointments() {
+        let mut series = Appointment { uid: Some("series@example.com".to_string()), ..series_with_override() };
+        series.exclude(at_nine(12));
+
+        let copy = series.duplicate(at_nine(26));
+        assert_eq!((copy.date, copy.description.as_str()), (at_nine(26), "Description"));
+        assert_eq!((copy.recurrence, copy.exceptions, copy.overrides, copy.uid), (None, vec![], vec![], None));
+        let copy = series.duplicate(at_nine(19));
+        assert_eq!((copy.date, copy.description.as_str()), (at_nine(19), "Changed"));
+        assert!(copy.overrides.is_empty());
+
+        let single = Appointment { id: 3, alerts: vec![Alert::Relative(Duration::minutes(-5))], ..Appointment::default() };
+        assert_eq!(single.duplicate(single.date), single);
+    }
+
+    #[test]
+    fn deletes_occurrences() {
+        let mut series = series_with_override();
+        series.exclude(at_nine(19));
+        assert!(series.overrides.is_empty());
+        assert!(series.occurrences_on(day(19)).is_empty());
+        assert_eq!(series.occurrences_on(day(26)).len(), 1);
+
+        series.exclude(at_nine(12));
+        assert_eq!(series.truncate_before(at_nine(26)), 3);
+        assert_eq!(series.exceptions, vec![at_nine(19), at_nine(12)]);
+        assert!(series.occurrences_on(day(26)).is_empty());
+        assert_eq!(series.occurrences_on(day(5)).len(), 1);
+
+        // Deleting from the first occurrence on leaves nothing, so the series is removed
+        assert_eq!(weekly(5).truncate_before(at_nine(5)), 0);
+        assert_eq!(Appointment::default().truncate_before(PDate::default()), 1);
+    }
+
+    #[test]
+    fn moves_appointments_and_occurrences() {
+        let single = Appointment {
+            alerts: vec![Alert::Absolute(at_nine(4)), Alert::Relative(Duration::minutes(-15))],
+            ..weekly(5)
+        };
+        let single = Appointment { recurrence: None, ..single };
+        let moved = single.moved_to(day(8));
+        assert_eq!((moved.date, moved.end), (at_nine(8), PDate::new(2026, 10, 8, 10, 0, 0)));
+        assert_eq!(moved.alerts, vec![Alert::Absolute(at_nine(7)), Alert::Relative(Duration::minutes(-15))]);
+
+        let mut series = series_with_override();
+        let moved = series.occurrence(at_nine(12)).appointment.moved_to(day(14));
+        series.override_occurrence(at_nine(12), moved);
+        assert!(series.occurrences_on(day(12)).is_empty());
+        let occurrences = series.occurrences_on(day(14));
+        assert_eq!(occurrences.len(), 1);
+        assert_eq!((occurrences[0].original, occurrences[0].appointment.date), (at_nine(12), at_nine(14)));
+        assert_eq!(occurrences[0].appointment.recurrence, None);
+        assert_eq!(series.occurrences_on(day(19))[0].appointment.description, "Changed");
+        assert_eq!(series.date, at_nine(5));
+    }
+
+    #[test]
+    fn moves_to_other_times() {
+        let meeting = Appointment {
+            end: PDate::new(2026, 10, 5, 10, 30, 0),
+            alerts: vec![Alert::Absolute(PDate::new(2026, 10, 4, 18, 0, 0)), Alert::Relative(Duration::minutes(-10))],
+            recurrence: None,
+            ..weekly(5)
+        };
+        let moved = meeting.moved_to_time(day(8), NaiveTime::from_hms_opt(14, 0, 0).unwrap());
+        assert_eq!((moved.date, moved.end), (PDate::new(2026, 10, 8, 14, 0, 0), PDate::new(2026, 10, 8, 15, 30, 0)));
+        // Absolute alerts keep their distance to the start, relative ones already do
+        assert_eq!(moved.alerts, vec![Alert::Absolute(PDate::new(2026, 10, 7, 23, 0, 0)), Alert::Relative(Duration::minutes(-10))]);
+
+        // Appointments do not reach into the next day
+        let late = meeting.moved_to_time(day(8), NaiveTime::from_hms_opt(23, 0, 0).unwrap());
+        assert_eq!((late.date, late.end), (PDate::new(2026, 10, 8, 23, 0, 0), PDate::new(2026, 10, 8, 23, 59, 0)));
+
+        let holiday = Appointment { date: PDate::new(2026, 10, 5, 0, 0, 0), end: PDate::new(2026, 10, 5, 0, 0, 0), ..Appointment::default() };
+        let moved = holiday.moved_to_time(day(6), NaiveTime::from_hms_opt(10, 0, 0).unwrap());
+        assert!(!moved.all_day);
+        assert_eq!((moved.date, moved.end), (PDate::new(2026, 10, 6, 10, 0, 0), PDate::new(2026, 10, 6, 11, 0, 0)));
+    }
+
+    #[test]
+    fn reads_priority_names() {
+        for priority in Priority::ALL {
+            assert_eq!(Priority::from_name(priority.name()), Some(*priority));
+        }
+        assert_eq!(Priority::from_name("MIDDLE"), Some(Priority::Middle));
+        // Translated names are only shown, not read
+        assert_eq!(Priority::from_name("hoch"), None);
+    }
+
+    fn tagged(tags: &[&str]) -> Appointment {
+        Appointment { tags: Some(tags.iter().map(|tag| tag.to_string()).collect()), ..Appointment::default() }
+    }
+
+    #[test]
+    fn renames_tags() {
+        let mut appointment = tagged(&["work", "urgent"]);
+        assert!(appointment.rename_tag("work", "job"));
+        assert_eq!(appointment, tagged(&["job", "urgent"]));
+        assert!(!appointment.rename_tag("work", "job"));
+        assert!(!tagged(&[]).rename_tag("work", "job"));
+
+        // Renaming to a tag the appointment already has merges both
+        let mut appointment = tagged(&["job", "work", "urgent"]);
+        assert!(appointment.rename_tag("work", "job"));
+        assert_eq!(appointment, tagged(&["job", "urgent"]));
+
+        let mut series = Appointment { recurrence: Some(Recurrence::default()), ..tagged(&["home"]) };
+        series.override_occurrence(series.date, tagged(&["work"]));
+        assert!(series.rename_tag("work", "job"));
+        assert_eq!(series.tags, Some(vec!["home".to_string()]));
+        assert_eq!(series.overrides[0].appointment.tags, Some(vec!["job".to_string()]));
+    }
+
+    #[test]
+    fn counts_tags() {
+        let mut series = Appointment { recurrence: Some(Recurrence::default()), ..tagged(&["work"]) };
+        // A tag of the series and of its modified occurrence counts once
+        series.override_occurrence(series.date, tagged(&["work", "moved"]));
+        let appointments = [series, tagged(&["work", "", "work"]), tagged(&["home"]), Appointment::default()];
+        assert_eq!(tag_counts(&appointments), vec![
+            ("home".to_string(), 1),
+            ("moved".to_string(), 1),
+            ("work".to_string(), 2),
+        ]);
+        assert!(tag_counts(&[]).is_empty());
+    }
+
+    #[test]
+    fn moving_a_series_moves_its_exceptions() {
+        let mut series = weekly(5);
+        series.exclude(PDate::new(2026, 10, 12, 9, 0, 0));
+        let mut changed = series.occurrence(PDate::new(2026, 10, 19, 9, 0, 0)).appointment;
+        changed.description = "Changed".to_string();
+        series.override_occurrence(PDate::new(2026, 10, 19, 9, 0, 0), changed);
+
+        // The monday series becomes a tuesday series at another time
+        let first = series.occurrence(PDate::new(2026, 10, 5, 9, 0, 0)).appointment;
+        let edited = Appointment { date: PDate::new(2026, 10, 6, 14, 0, 0), end: PDate::new(2026, 10, 6, 15, 0, 0), ..first };
+        series.edit_series(PDate::new(2026, 10, 5, 9, 0, 0), edited);
+
+        assert_eq!(series.date, PDate::new(2026, 10, 6, 14, 0, 0));
+        assert_eq!(series.exceptions, vec![PDate::new(2026, 10, 13, 14, 0, 0)]);
+        assert!(series.occurrences_on(NaiveDate::from_ymd_opt(2026, 10, 13).unwrap()).is_empty());
+        let moved = series.occurrences_on(NaiveDate::from_ymd_opt(2026, 10, 20).unwrap());
+        assert_eq!(moved.len(), 1);
+        assert_eq!(moved[0].appointment.description, "Changed");
+        assert_eq!(moved[0].original, PDate::new(2026, 10, 20, 14, 0, 0));
+        assert_eq!(series.occurrence(moved[0].original).appointment.description, "Changed");
+        assert_eq!(series.occurrences_on(NaiveDate::from_ymd_opt(2026, 10, 27).unwrap())[0].appointment.description, "Description");
+    }
+}
@@ -0,0 +1,474 @@
+use chrono::{Days, Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Weekday};
+
+use super::date_format::{self, next_weekday, parse_weekday};
+use super::{date, Alert, Appointment, ByDay, Frequency, PDate, Priority, Recurrence};
+
+/// An appointment read from a single line like "Dentist tomorrow 14:30 #health !high remind 1d before"
+///
+/// Words that are not understood as date, time, tag, priority, reminder or
+/// recurrence make up the description. `problems` lists what could not be read
+/// unambiguously, such input is better completed in the full dialog.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuickAdd {
+    pub appointment: Appointment,
+    pub problems: Vec<String>,
+}
+
+/// Length of timed appointments without an end
+const DEFAULT_LENGTH: i64 = 60;
+
+pub fn parse(input: &str, now: NaiveDateTime) -> QuickAdd {
+    let mut parser = Parser { today: now.date(), ..Parser::default() };
+    let words: Vec<&str> = input.split_whitespace().collect();
+    let mut i = 0;
+    while i < words.len() {
+        i += parser.word(&words[i..]);
+    }
+    parser.finish()
+}
+
+#[derive(Default)]
+struct Parser {
+    today: NaiveDate,
+    description: Vec<String>,
+    date: Option<NaiveDate>,
+    start: Option<NaiveTime>,
+    end: Option<NaiveTime>,
+    length: Option<Duration>,
+    priority: Option<Priority>,
+    tags: Vec<String>,
+    alerts: Vec<Alert>,
+    recurrence: Option<Recurrence>,
+    problems: Vec<String>,
+}
+
+impl Parser {
+    /// Reads the meaning of the first of `words` and returns how many words were used
+    fn word(&mut self, words: &[&str]) -> usize {
+        let word = words[0];
+        let lower = word.to_lowercase();
+        let next = words.get(1).map(|word| word.to_lowercase()).unwrap_or_default();
+
+        if let Some(tag) = word.strip_prefix('#').filter(|tag| !tag.is_empty()) {
+            self.tags.push(tag.to_string());
+            return 1
+        }
+        if let Some(priority) = lower.strip_prefix('!').filter(|priority| !priority.is_empty()) {
+            match parse_priority(priority) {
+                Some(priority) => self.set_priority(priority),
+                None => self.problems.push(t!("quick-add-unknown-priority", word = word)),
+            }
+            return 1
+        }
+        match lower.as_str() {
+            "remind" | "reminder" => return 1 + self.reminder(&words[1..]),
+            "every" => return 1 + self.recurrence(&words[1..]),
+            "daily" => return self.set_recurrence(Frequency::Daily, 1, vec![]),
+            "weekly" => return self.set_recurrence(Frequency::Weekly, 1, vec![]),
+            "monthly" => return self.set_recurrence(Frequency::Monthly, 1, vec![]),
+            "yearly" | "annually" => return self.set_recurrence(Frequency::Yearly, 1, vec![]),
+            "for" => {
+                if let Some((length, used)) = parse_amount(&words[1..]) {
+                    self.length = Some(length);
+                    return 1 + used
+                }
+                if is_number(&next) {
+                    return 1 + self.invalid_amount(&words[1..])
+                }
+            }
+            "in" => {
+                match parse_days(&words[1..]).and_then(|(days, used)| Some((self.today.checked_add_days(days)?, used))) {
+                    Some((date, used)) => {
+                        self.set_date(date);
+                        return 1 + used
+                    }
+                    None if is_number(&next) => return 1 + self.invalid_amount(&words[1..]),
+                    None => {}
+                }
+            }
+            "next" => {
+                if let Some(weekday) = parse_weekday(&next) {
+                    self.set_date(next_weekday(self.today + Days::new(1), weekday));
+                    return 2
+                }
+            }
+            // Only a connector if a date or time follows, "Dinner at Luigi's" keeps its "at"
+            "at" | "on" if parse_time_range(&next).is_some() || date_format::parse_date(&next, self.today).is_some() => {
+                return 1
+            }
+            _ => {}
+        }
+        if let Some((start, end)) = parse_time_range(&lower) {
+            if self.start.is_some_and(|x| x != start) {
+                self.problems.push(t!("quick-add-two-times", word = word));
+            }
+            self.start = Some(start);
+            self.end = end.or(self.end);
+            return 1
+        }
+        if let Some(date) = date_format::parse_date(&lower, self.today) {
+            self.set_date(date);
+            return 1
+        }
+        self.description.push(word.to_string());
+        1
+    }
+
+    /// Reports an amount like "-1 day" or one too large for a date, and returns how many words it has
+    fn invalid_amount(&mut self, words: &[&str]) -> usize {
+        self.problems.push(t!("quick-add-invalid-amount", word = words.join(" ")));
+        match words.get(1).is_some_and(|word| unit(&word.to_lowercase()).is_some()) {
+            true => 2,
+            false => 1,
+        }
+    }
+
+    fn set_date(&mut self, date: NaiveDate) {
+        if self.date.is_some_and(|x| x != date) {
+            self.problems.push(t!("quick-add-two-dates"));
+        }
+        self.date = Some(date);
+    }
+
+    fn set_priority(&mut self, priority: Priority) {
+        if self.priority.is_some_and(|x| x != priority) {
+            self.problems.push(t!("quick-add-two-priorities"));
+        }
+        self.priority = Some(priority);
+    }
+
+    fn set_recurrence(&mut self, frequency: Frequency, interval: u32, by_day: Vec<ByDay>) -> usize {
+        if self.recurrence.is_some() {
+            self.problems.push(t!("quick-add-two-repetitions"));
+        }
+        self.recurrence = Some(Recurrence { frequency, interval, by_day, ..Recurrence::default() });
+        1
+    }
+
+    /// "remind 1d before", "remind 15 min before", the "before" is optional
+    fn reminder(&mut self, words: &[&str]) -> usize {
+        let Some((offset, mut used)) = parse_amount(words) else {
+            self.problems.push(t!("quick-add-reminder-time"));
+            return 0
+        };
+        if words.get(used).is_some_and(|word| word.eq_ignore_ascii_case("before")) {
+            used += 1;
+        }
+        self.alerts.push(Alert::Relative(-offset));
+        used
+    }
+
+    /// "every day", "every 2 weeks", "every weekday", "every monday and thursday"
+    fn recurrence(&mut self, words: &[&str]) -> usize {
+        let (interval, mut used) = match words.first().and_then(|word| word.parse::<u32>().ok()) {
+            Some(interval) if interval > 0 => (interval, 1),
+            _ => (1, 0),
+        };
+        let unit = words.get(used).map(|word| word.to_lowercase()).unwrap_or_default();
+        let frequency = match unit.trim_end_matches('s') {
+            "day" => Some(Frequency::Daily),
+            "week" => Some(Frequency::Weekly),
+            "month" => Some(Frequency::Monthly),
+            "year" => Some(Frequency::Yearly),
+            _ => None,
+        };
+        if let Some(frequency) = frequency {
+            self.set_recurrence(frequency, interval, vec![]);
+            return used + 1
+        }
+        let mut weekdays = vec![];
+        if unit == "weekday" || unit == "weekdays" {
+            weekdays = vec![Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri];
+            used += 1;
+        } else {
+            while let Some(word) = words.get(used).map(|word| word.to_lowercase()) {
+                let word = word.trim_end_matches(',');
+                if let Some(weekday) = parse_weekday(word) {
+                    weekdays.push(weekday);
+                } else if word != "and" || weekdays.is_empty() {
+                    break
+                }
+                used += 1;
+            }
+        }
+        if weekdays.is_empty() {
+            self.problems.push(t!("quick-add-every"));
+            return used
+        }
+        if self.date.is_none() {
+            let first = weekdays.iter().map(|weekday| next_weekday(self.today, *weekday)).min();
+            self.date = first;
+        }
+        let by_day = weekdays.into_iter().map(|weekday| ByDay { ordinal: 0, weekday }).collect();
+        self.set_recurrence(Frequency::Weekly, interval, by_day);
+        used
+    }
+
+    fn finish(mut self) -> QuickAdd {
+        if self.description.is_empty() {
+            self.problems.push(t!("quick-add-no-description"));
+        }
+        let day = self.date.unwrap_or(self.today);
+        let (all_day, start, end) = match self.start {
+            Some(start) => {
+                let length = self.length.unwrap_or(Duration::minutes(DEFAULT_LENGTH));
+                let end = match self.end {
+                    Some(end) => end,
+                    None => {
+                        let (end, overflow) = start.overflowing_add_signed(length);
+                        if overflow != 0 {
+                            self.problems.push(t!("quick-add-after-midnight"));
+                        }
+                        end.max(start)
+                    }
+                };
+                if end < start {
+                    self.problems.push(t!("quick-add-ends-before-start"));
+                }
+                (false, start, end)
+            }
+            None => {
+                if self.length.is_some() || self.end.is_some() {
+                    self.problems.push(t!("quick-add-length-needs-start"));
+                }
+                (true, NaiveTime::MIN, NaiveTime::MIN)
+            }
+        };
+        let at = |time: NaiveTime| PDate { hour: time.hour(), min: time.minute(), sec: 0, ..date::naive_date_time_to_p_date(day.and_time(NaiveTime::MIN)) };
+        QuickAdd {
+            appointment: Appointment {
+                id: 0,
+                date: at(start),
+                end: at(end),
+                all_day,
+                priority: self.priority.unwrap_or(Priority::Low),
+                alerts: self.alerts,
+                tags: Some(self.tags),
+                description: self.description.join(" "),
+                recurrence: self.recurrence,
+                exceptions: vec![],
+                overrides: vec![],
+                calendar: None,
+                uid: None,
+            },
+            problems: self.problems,
+        }
+    }
+}
+
+fn parse_priority(word: &str) -> Option<Priority> {
+    match word {
+        "high" | "h" | "1" | "!" => Some(Priority::High),
+        "middle" | "medium" | "m" | "2" => Some(Priority::Middle),
+        "low" | "l" | "3" => Some(Priority::Low),
+        _ => None,
+    }
+}
+
+/// A time like `14:30`, `9`, `2pm` or `2:30pm`, or a range like `14:30-15:30`
+fn parse_time_range(word: &str) -> Option<(NaiveTime, Option<NaiveTime>)> {
+    if let Some((start, end)) = word.split_once('-') {
+        return Some((parse_time(start, true)?, Some(parse_time(end, true)?)))
+    }
+    Some((parse_time(word, false)?, None))
+}
+
+/// Bare numbers are only read as hours inside a range, "Call 3 people" has no time
+fn parse_time(word: &str, bare_hours: bool) -> Option<NaiveTime> {
+    let (word, offset) = match (word.strip_suffix("am"), word.strip_suffix("pm")) {
+        (Some(word), _) => (word, Some(0)),
+        (_, Some(word)) => (word, Some(12)),
+        _ => (word, None),
+    };
+    let (hour, min) = match word.split_once(':') {
+        Some((hour, min)) if min.len() == 2 => (hour.parse::<u32>().ok()?, min.parse::<u32>().ok()?),
+        Some(_) => return None,
+        None if offset.is_some() || bare_hours => (word.parse::<u32>().ok()?, 0),
+        None => return None,
+    };
+    let hour = match offset {
+        Some(_) if hour == 0 || hour > 12 => return None,
+        Some(offset) => hour % 12 + offset,
+        None => hour,
+    };
+    NaiveTime::from_hms_opt(hour, min, 0)
+}
+
+/// An amount of time like `1d`, `1h30m`, `15 min` or `2 weeks`, with the number of words used
+fn parse_amount(words: &[&str]) -> Option<(Duration, usize)> {
+    let first = words.first()?.to_lowercase();
+    if let Some(duration) = parse_compact(&first) {
+        return Some((duration, 1))
+    }
+    let number = first.parse::<u32>().ok()?;
+    let unit = unit(&words.get(1)?.to_lowercase())?;
+    Some((unit.checked_mul(i32::try_from(number).ok()?)?, 2))
+}
+
+/// An amount without spaces like `1d` or `1h30m`
+fn parse_compact(word: &str) -> Option<Duration> {
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    let mut rest = word;
+    while let Some(c) = rest.chars().next() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            rest = &rest[1..];
+            continue
+        }
+        let end = rest.find(|c: char| c.is_ascii_digit()).unwrap_or(rest.len());
+        let amount = unit(&rest[..end])?.checked_mul(number.parse::<i32>().ok()?)?;
+        total = total.checked_add(&amount)?;
+        number.clear();
+        rest = &rest[end..];
+    }
+    if !number.is_empty() || total.is_zero() {
+        return None
+    }
+    Some(total)
+}
+
+fn unit(word: &str) -> Option<Duration> {
+    match word.trim_end_matches('s') {
+        "m" | "min" | "minute" => Some(Duration::minutes(1)),
+        "h" | "hour" => Some(Duration::hours(1)),
+        "d" | "day" => Some(Duration::days(1)),
+        "w" | "week" => Some(Duration::weeks(1)),
+        _ => None,
+    }
+}
+
+/// "3 days" or "2 weeks" after "in"
+fn parse_days(words: &[&str]) -> Option<(Days, usize)> {
+    let (duration, used) = parse_amount(words)?;
+    if duration.num_seconds() % 86400 != 0 {
+        return None
+    }
+    Some((Days::new(u64::try_from(duration.num_days()).ok()?), used))
+}
+
+/// Whether the word starts like an amount, so it was meant as one even if it can not be used
+fn is_number(word: &str) -> bool {
+    word.trim_start_matches('-').starts_with(|c: char| c.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sunday, 18.10.2026 at noon
+    fn now() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 10, 18).unwrap().and_hms_opt(12, 0, 0).unwrap()
+    }
+
+    fn day(day: u32, month: u32, year: i32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn time(hour: u32, min: u32) -> Option<NaiveTime> {
+        NaiveTime::from_hms_opt(hour, min, 0)
+    }
+
+    #[test]
+    fn parses_full_example() {
+        let QuickAdd { appointment, problems } = parse("Dentist tomorrow 14:30 #health !high remind 1d before", now());
+        assert!(problems.is_empty(), "{:?}", problems);
+        assert_eq!(appointment.description, "Dentist");
+        assert_eq!(appointment.date.date(), day(19, 10, 2026));
+        assert!(!appointment.all_day);
+        assert_eq!(appointment.start_time(), time(14, 30));
+        assert_eq!(appointment.end_time(), time(15, 30));
+        assert_eq!(appointment.tags, Some(vec!["health".to_string()]));
+        assert_eq!(appointment.priority, Priority::High);
+        assert_eq!(appointment.alerts, vec![Alert::Relative(Duration::days(-1))]);
+        assert_eq!(appointment.recurrence, None);
+    }
+
+    #[test]
+    fn parses_weekly_recurrence() {
+        let QuickAdd { appointment, problems } = parse("Team sync every Monday 10:00", now());
+        assert!(problems.is_empty(), "{:?}", problems);
+        assert_eq!(appointment.description, "Team sync");
+        assert_eq!(appointment.date.date(), day(19, 10, 2026));
+        assert_eq!(appointment.start_time(), time(10, 0));
+        let recurrence = appointment.recurrence.unwrap();
+        assert_eq!(recurrence.frequency, Frequency::Weekly);
+        assert_eq!(recurrence.by_day, vec![ByDay { ordinal: 0, weekday: Weekday::Mon }]);
+    }
+
+    #[test]
+    fn parses_other_recurrences() {
+        let recurrence = |input: &str| parse(input, now()).appointment.recurrence.unwrap();
+        assert_eq!(recurrence("Rent every month").frequency, Frequency::Monthly);
+        assert_eq!(recurrence("Gym every 2 weeks").interval, 2);
+        assert_eq!(recurrence("Water plants daily").frequency, Frequency::Daily);
+        assert_eq!(recurrence("Standup every weekday 9:15").by_day.len(), 5);
+        let twice = recurrence("Yoga every tue and thu 18:00");
+        assert_eq!(twice.by_day.iter().map(|x| x.weekday).collect::<Vec<_>>(), vec![Weekday::Tue, Weekday::Thu]);
+        assert_eq!(parse("Yoga every tue and thu", now()).appointment.date.date(), day(20, 10, 2026));
+    }
+
+    #[test]
+    fn parses_dates() {
+        let date = |input: &str| parse(input, now()).appointment.date.date();
+        assert_eq!(date("Call mum"), day(18, 10, 2026));
+        assert_eq!(date("Call mum today"), day(18, 10, 2026));
+        assert_eq!(date("Party 31.10.2026"), day(31, 10, 2026));
+        assert_eq!(date("Party 2026-10-31"), day(31, 10, 2026));
+        assert_eq!(date("Christmas 24.12."), day(24, 12, 2026));
+        assert_eq!(date("Birthday 1.2."), day(1, 2, 2027));
+        assert_eq!(date("Review friday"), day(23, 10, 2026));
+        assert_eq!(date("Review on sunday"), day(18, 10, 2026));
+        assert_eq!(date("Review next sunday"), day(25, 10, 2026));
+        assert_eq!(date("Checkup in 2 weeks"), day(1, 11, 2026));
+        assert_eq!(date("Checkup in 3d"), day(21, 10, 2026));
+        assert_eq!(date("Renew passport +1mo"), day(18, 11, 2026));
+    }
+
+    #[test]
+    fn parses_times() {
+        let times = |input: &str| {
+            let appointment = parse(input, now()).appointment;
+            (appointment.start_time(), appointment.end_time())
+        };
+        assert_eq!(times("Lunch 12:00-13:30"), (time(12, 0), time(13, 30)));
+        assert_eq!(times("Lunch 12-13"), (time(12, 0), time(13, 0)));
+        assert_eq!(times("Call at 2pm"), (time(14, 0), time(15, 0)));
+        assert_eq!(times("Call 9:30am for 15m"), (time(9, 30), time(9, 45)));
+        assert_eq!(times("Call 9:30am for 1m"), (time(9, 30), time(9, 31)));
+        assert_eq!(times("Workshop 9:00 for 1h30m"), (time(9, 0), time(10, 30)));
+        assert_eq!(times("Call 3 people"), (None, None));
+    }
+
+    #[test]
+    fn keeps_unknown_words_in_description() {
+        let QuickAdd { appointment, problems } = parse("Dinner at Luigi's on the terrace", now());
+        assert!(problems.is_empty());
+        assert_eq!(appointment.description, "Dinner at Luigi's on the terrace");
+        assert!(appointment.all_day);
+    }
+
+    #[test]
+    fn parses_several_reminders() {
+        let alerts = parse("Flight 24.12. 6:00 remind 1 week before remind 2h", now()).appointment.alerts;
+        assert_eq!(alerts, vec![Alert::Relative(Duration::weeks(-1)), Alert::Relative(Duration::hours(-2))]);
+    }
+
+    #[test]
+    fn reports_ambiguities() {
+        let problems = |input: &str| parse(input, now()).problems;
+        assert_eq!(problems("tomorrow 10:00").len(), 1);
+        assert_eq!(problems("Meeting tomorrow friday").len(), 1);
+        assert_eq!(problems("Meeting 10:00 11:00").len(), 1);
+        assert_eq!(problems("Meeting !urgent").len(), 1);
+        assert_eq!(problems("Meeting remind soon").len(), 1);
+        assert_eq!(problems("Meeting every now and then").len(), 1);
+        assert_eq!(problems("Meeting 23:30 for 2h").len(), 1);
+        assert_eq!(problems("Meeting for 2h").len(), 1);
+        assert_eq!(problems("Checkup in -1 day"), vec![t!("quick-add-invalid-amount", word = "-1 day")]);
+        assert_eq!(problems("Checkup in 100000000 weeks").len(), 1);
+        assert_eq!(problems("Checkup 10:00 for 2000000000w").len(), 1);
+        assert_eq!(problems("Checkup 10:00 for 99999999999 min").len(), 1);
+        assert_eq!(parse("Checkup in -1 day", now()).appointment.description, "Checkup");
+    }
+}
@@ -0,0 +1,400 @@
+use std::fmt;
+
+use chrono::{Datelike, Days, Months, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+
+use super::PDate;
+
+/// How often a recurring appointment repeats, the FREQ part of an RRULE
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+impl Frequency {
+    pub const ALL: &[Self] = &[Self::Daily, Self::Weekly, Self::Monthly, Self::Yearly];
+}
+
+impl fmt::Display for Frequency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Frequency::Daily => t!("frequency-daily"),
+            Frequency::Weekly => t!("frequency-weekly"),
+            Frequency::Monthly => t!("frequency-monthly"),
+            Frequency::Yearly => t!("frequency-yearly"),
+        }
+        .fmt(f)
+    }
+}
+
+/// One entry of the BYDAY list, e.g. `MO`, `3MO` or `-1FR`
+///
+/// The ordinal is only used for monthly and yearly rules, `0` means every such weekday.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(try_from = "String", into = "String")]
+pub struct ByDay {
+    pub ordinal: i32,
+    pub weekday: Weekday,
+}
+
+impl fmt::Display for ByDay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let day = match self.weekday {
+            Weekday::Mon => "MO",
+            Weekday::Tue => "TU",
+            Weekday::Wed => "WE",
+            Weekday::Thu => "TH",
+            Weekday::Fri => "FR",
+            Weekday::Sat => "SA",
+            Weekday::Sun => "SU",
+        };
+        if self.ordinal == 0 {
+            write!(f, "{}", day)
+        } else {
+            write!(f, "{}{}", self.ordinal, day)
+        }
+    }
+}
+
+impl TryFrom<String> for ByDay {
+    type Error = String;
+
+    fn try_from(string: String) -> Result<Self, Self::Error> {
+        let trimmed = string.trim().to_uppercase();
+        if trimmed.len() < 2 {
+            return Err(string)
+        }
+        let (ordinal, day) = trimmed.split_at(trimmed.len() - 2);
+        let weekday = match day {
+            "MO" => Weekday::Mon,
+            "TU" => Weekday::Tue,
+            "WE" => Weekday::Wed,
+            "TH" => Weekday::Thu,
+            "FR" => Weekday::Fri,
+            "SA" => Weekday::Sat,
+            "SU" => Weekday::Sun,
+            _ => return Err(string)
+        };
+        let ordinal = match ordinal.trim_start_matches('+') {
+            "" => 0,
+            number => match number.parse::<i32>() {
+                Ok(number) if number != 0 && number.abs() <= 53 => number,
+                _ => return Err(string)
+            }
+        };
+        Ok(ByDay { ordinal, weekday })
+    }
+}
+
+impl From<ByDay> for String {
+    fn from(value: ByDay) -> Self {
+        value.to_string()
+    }
+}
+
+/// A recurrence rule following the semantics of RFC 5545 RRULEs
+///
+/// Weeks start on Monday (WKST=MO). A yearly rule with BYDAY picks the days
+/// within the month of the first occurrence, as if BYMONTH was set to it.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct Recurrence {
+    pub frequency: Frequency,
+    pub interval: u32,
+    pub by_day: Vec<ByDay>,
+    pub count: Option<u32>,
+    pub until: Option<PDate>,
+}
+
+impl Default for Recurrence {
+    fn default() -> Self {
+        Recurrence { frequency: Frequency::Weekly, interval: 1, by_day: vec![], count: None, until: None }
+    }
+}
+
+impl Recurrence {
+    /// The rule as the value of an iCalendar RRULE property
+    pub fn to_rrule(&self) -> String {
+        let frequency = match self.frequency {
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+            Frequency::Yearly => "YEARLY",
+        };
+        let mut rule = format!("FREQ={}", frequency);
+        if self.interval > 1 {
+            rule.push_str(&format!(";INTERVAL={}", self.interval));
+        }
+        if !self.by_day.is_empty() {
+            let days: Vec<String> = self.by_day.iter().map(ByDay::to_string).collect();
+            rule.push_str(&format!(";BYDAY={}", days.join(",")));
+        }
+        if let Some(count) = self.count {
+            rule.push_str(&format!(";COUNT={}", count));
+        }
+        if let Some(until) = self.until {
+            rule.push_str(&format!(";UNTIL={:04}{:02}{:02}", until.year, until.month, until.day));
+        }
+        rule
+    }
+
+    /// Parses the value of an iCalendar RRULE property
+    ///
+    /// Parts Planer cannot represent, like BYMONTH or BYSETPOS, are rejected.
+    pub fn from_rrule(rule: &str) -> Result<Recurrence, String> {
+        let mut recurrence = Recurrence { frequency: Frequency::Daily, ..Recurrence::default() };
+        let mut has_frequency = false;
+        for part in rule.split(';').filter(|part| !part.is_empty()) {
+            let Some((key, value)) = part.split_once('=') else {
+                return Err(rule.to_string())
+            };
+            match key.to_uppercase().as_str() {
+                "FREQ" => {
+                    recurrence.frequency = match value.to_uppercase().as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        _ => return Err(rule.to_string())
+                    };
+                    has_frequency = true;
+                }
+                "INTERVAL" => recurrence.interval = value.parse().map_err(|_| rule.to_string())?,
+                "COUNT" => recurrence.count = Some(value.parse().map_err(|_| rule.to_string())?),
+                "UNTIL" => {
+                    let date = value.get(0..8)
+                        .and_then(|date| NaiveDate::parse_from_str(date, "%Y%m%d").ok())
+                        .ok_or(rule.to_string())?;
+                    recurrence.until = Some(PDate::new(date.year(), date.month(), date.day(), 0, 0, 0));
+                }
+                "BYDAY" => {
+                    recurrence.by_day = value.split(',')
+                        .map(|day| ByDay::try_from(day.to_string()))
+                        .collect::<Result<Vec<ByDay>, String>>()?;
+                }
+                "WKST" if value.eq_ignore_ascii_case("MO") => {}
+                _ => return Err(rule.to_string())
+            }
+        }
+        if !has_frequency {
+            return Err(rule.to_string())
+        }
+        Ok(recurrence)
+    }
+
+    /// All occurrences of the rule for a series starting at `start`, in ascending order
+    pub fn occurrences(&self, start: NaiveDate) -> Occurrences<'_> {
+        Occurrences { rule: self, start, period: 0, pending: vec![], emitted: 0, empty_periods: 0 }
+    }
+
+    /// Whether the series starting at `start` has an occurrence on `day`
+    pub fn occurs_on(&self, start: NaiveDate, day: NaiveDate) -> bool {
+        if day < start || self.until.is_some_and(|until| day > until.date()) {
+            return false
+        }
+        if self.count.is_some() {
+            // The count can only be checked by walking the series from the start
+            return self.occurrences(start).take_while(|date| *date <= day).any(|date| date == day)
+        }
+        let interval = self.interval.max(1) as i64;
+        let period = match self.frequency {
+            Frequency::Daily => day.signed_duration_since(start).num_days(),
+            Frequency::Weekly => week_start(day).signed_duration_since(week_start(start)).num_days() / 7,
+            Frequency::Monthly => (day.year() - start.year()) as i64 * 12 + day.month() as i64 - start.month() as i64,
+            Frequency::Yearly => (day.year() - start.year()) as i64,
+        };
+        period % interval == 0 && self.period_dates(start, (period / interval) as u32).contains(&day)
+    }
+
+    /// Dates of the `index`th period of the series, before COUNT and UNTIL are applied
+    fn period_dates(&self, start: NaiveDate, index: u32) -> Vec<NaiveDate> {
+        let step = index.saturating_mul(self.interval.max(1));
+        let mut dates = match self.frequency {
+            Frequency::Daily => {
+                start.checked_add_days(Days::new(step as u64))
+                    .filter(|date| self.by_day.is_empty() || self.by_day.iter().any(|by_day| by_day.weekday == date.weekday()))
+                    .into_iter()
+                    .collect()
+            }
+            Frequency::Weekly => {
+                let Some(week) = week_start(start).checked_add_days(Days::new(step as u64 * 7)) else {
+                    return vec![]
+                };
+                if self.by_day.is_empty() {
+                    week.checked_add_days(Days::new(start.weekday().num_days_from_monday() as u64))
+                        .into_iter()
+                        .collect()
+                } else {
+                    self.by_day.iter()
+                        .filter_map(|by_day| week.checked_add_days(Days::new(by_day.weekday.num_days_from_monday() as u64)))
+                        .collect()
+                }
+            }
+            Frequency::Monthly => {
+                let Some(month) = first_of_month(start).checked_add_months(Months::new(step)) else {
+                    return vec![]
+                };
+                self.month_dates(month, start.day())
+            }
+            Frequency::Yearly => {
+                let Some(month) = first_of_month(start).checked_add_months(Months::new(step.saturating_mul(12))) else {
+                    return vec![]
+                };
+                self.month_dates(month, start.day())
+            }
+        };
+        dates.retain(|date| *date >= start);
+        dates.sort();
+        dates.dedup();
+        dates
+    }
+
+    /// Dates in the month starting at `month` matching BYDAY, or the given day of the month
+    fn month_dates(&self, month: NaiveDate, day: u32) -> Vec<NaiveDate> {
+        if self.by_day.is_empty() {
+            return NaiveDate::from_ymd_opt(month.year(), month.month(), day).into_iter().collect()
+        }
+        let days: Vec<NaiveDate> = month.iter_days().take_while(|date| date.month() == month.month()).collect();
+        let mut dates = vec![];
+        for by_day in &self.by_day {
+            let matching: Vec<&NaiveDate> = days.iter().filter(|date| date.weekday() == by_day.weekday).collect();
+            match by_day.ordinal {
+                0 => dates.extend(matching),
+                n if n > 0 => dates.extend(matching.get(n as usize - 1).copied()),
+                n => dates.extend(matching.len().checked_sub(n.unsigned_abs() as usize).and_then(|i| matching.get(i).copied())),
+            }
+        }
+        dates
+    }
+}
+
+/// Iterator over the occurrences of a [`Recurrence`]
+pub struct Occurrences<'a> {
+    rule: &'a Recurrence,
+    start: NaiveDate,
+    period: u32,
+    pending: Vec<NaiveDate>,
+    emitted: u32,
+    empty_periods: u32,
+}
+
+impl Iterator for Occurrences<'_> {
+    type Item = NaiveDate;
+
+    fn next(&mut self) -> Option<NaiveDate> {
+        if self.rule.count.is_some_and(|count| self.emitted >= count) {
+            return None
+        }
+        while self.pending.is_empty() {
+            // Rules like "every 5th monday" leave some periods empty, but never this many
+            if self.empty_periods > 1000 {
+                return None
+            }
+            self.pending = self.rule.period_dates(self.start, self.period);
+            self.pending.reverse();
+            self.period += 1;
+            self.empty_periods += 1;
+        }
+        self.empty_periods = 0;
+        let date = self.pending.pop()?;
+        if self.rule.until.is_some_and(|until| date > until.date()) {
+            return None
+        }
+        self.emitted += 1;
+        Some(date)
+    }
+}
+
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date.checked_sub_days(Days::new(date.weekday().num_days_from_monday() as u64)).unwrap_or(date)
+}
+
+fn first_of_month(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    /// The occurrences of `rule` starting at `start` as `yyyy-mm-dd`, at most 100 of them
+    fn expand(rule: &str, start: NaiveDate) -> Vec<String> {
+        let recurrence = Recurrence::from_rrule(rule).unwrap();
+        let dates: Vec<NaiveDate> = recurrence.occurrences(start).take(100).collect();
+        // Looking up single days has to agree with walking the series
+        for date in start.iter_days().take_while(|date| date <= dates.last().unwrap()) {
+            assert_eq!(recurrence.occurs_on(start, date), dates.contains(&date), "{} on {}", rule, date);
+        }
+        dates.iter().map(|date| date.format("%Y-%m-%d").to_string()).collect()
+    }
+
+    #[test]
+    fn expands_daily_and_weekly_rules() {
+        // Examples of RFC 5545, section 3.8.5.3
+        assert_eq!(expand("FREQ=DAILY;COUNT=10", day(1997, 9, 2)).len(), 10);
+        assert_eq!(expand("FREQ=DAILY;INTERVAL=10;COUNT=5", day(1997, 9, 2)),
+            ["1997-09-02", "1997-09-12", "1997-09-22", "1997-10-02", "1997-10-12"]);
+        assert_eq!(expand("FREQ=WEEKLY;COUNT=10;BYDAY=TU,TH", day(1997, 9, 2)),
+            ["1997-09-02", "1997-09-04", "1997-09-09", "1997-09-11", "1997-09-16",
+             "1997-09-18", "1997-09-23", "1997-09-25", "1997-09-30", "1997-10-02"]);
+        assert_eq!(expand("FREQ=WEEKLY;INTERVAL=2;UNTIL=19971223;BYDAY=MO,WE,FR", day(1997, 9, 1)),
+            ["1997-09-01", "1997-09-03", "1997-09-05", "1997-09-15", "1997-09-17",
+             "1997-09-19", "1997-09-29", "1997-10-01", "1997-10-03", "1997-10-13",
+             "1997-10-15", "1997-10-17", "1997-10-27", "1997-10-29", "1997-10-31",
+             "1997-11-10", "1997-11-12", "1997-11-14", "1997-11-24", "1997-11-26",
+             "1997-11-28", "1997-12-08", "1997-12-10", "1997-12-12", "1997-12-22"]);
+    }
+
+    #[test]
+    fn expands_monthly_rules_with_ordinals() {
+        assert_eq!(expand("FREQ=MONTHLY;COUNT=10;BYDAY=1FR", day(1997, 9, 5)),
+            ["1997-09-05", "1997-10-03", "1997-11-07", "1997-12-05", "1998-01-02",
+             "1998-02-06", "1998-03-06", "1998-04-03", "1998-05-01", "1998-06-05"]);
+        assert_eq!(expand("FREQ=MONTHLY;INTERVAL=2;COUNT=10;BYDAY=1SU,-1SU", day(1997, 9, 7)),
+            ["1997-09-07", "1997-09-28", "1997-11-02", "1997-11-30", "1998-01-04",
+             "1998-01-25", "1998-03-01", "1998-03-29", "1998-05-03", "1998-05-31"]);
+        assert_eq!(expand("FREQ=MONTHLY;COUNT=6;BYDAY=-2MO", day(1997, 9, 22)),
+            ["1997-09-22", "1997-10-20", "1997-11-17", "1997-12-22", "1998-01-19", "1998-02-16"]);
+        // Months without a fifth monday are skipped
+        assert_eq!(expand("FREQ=MONTHLY;COUNT=4;BYDAY=5MO", day(2026, 3, 30)),
+            ["2026-03-30", "2026-06-29", "2026-08-31", "2026-11-30"]);
+    }
+
+    #[test]
+    fn skips_missing_days() {
+        assert_eq!(expand("FREQ=MONTHLY;COUNT=4", day(2026, 1, 31)),
+            ["2026-01-31", "2026-03-31", "2026-05-31", "2026-07-31"]);
+        assert_eq!(expand("FREQ=YEARLY;COUNT=3", day(2024, 2, 29)),
+            ["2024-02-29", "2028-02-29", "2032-02-29"]);
+    }
+
+    #[test]
+    fn stops_at_until_and_empty_rules() {
+        let rule = Recurrence::from_rrule("FREQ=WEEKLY;UNTIL=20261027").unwrap();
+        assert_eq!(rule.occurrences(day(2026, 10, 6)).count(), 4);
+        assert!(rule.occurs_on(day(2026, 10, 6), day(2026, 10, 27)));
+        assert!(!rule.occurs_on(day(2026, 10, 6), day(2026, 11, 3)));
+        assert!(!rule.occurs_on(day(2026, 10, 6), day(2026, 9, 29)));
+
+        // Every seventh day from a tuesday is never a monday, the iterator still ends
+        let never = Recurrence::from_rrule("FREQ=DAILY;INTERVAL=7;BYDAY=MO").unwrap();
+        assert_eq!(never.occurrences(day(2026, 10, 6)).next(), None);
+    }
+
+    #[test]
+    fn reads_and_writes_rrules() {
+        let rule = "FREQ=MONTHLY;INTERVAL=2;BYDAY=1SU,-1SU;COUNT=10";
+        assert_eq!(Recurrence::from_rrule(rule).unwrap().to_rrule(), rule);
+        assert_eq!(Recurrence::from_rrule("freq=weekly;until=20261231T235959Z").unwrap().to_rrule(), "FREQ=WEEKLY;UNTIL=20261231");
+        assert!(Recurrence::from_rrule("FREQ=YEARLY;BYMONTH=3").is_err());
+        assert!(Recurrence::from_rrule("INTERVAL=2").is_err());
+        assert!(Recurrence::from_rrule("FREQ=MONTHLY;BYDAY=0MO").is_err());
+    }
+}
@@ -0,0 +1,215 @@
+use std::fmt;
+
+use chrono::{Days, Duration, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+use super::{date, Appointment, AppointmentKey, PDate};
+
+/// How many days ahead occurrences are checked for due alerts
+pub const LOOKAHEAD_DAYS: u64 = 366;
+/// How long a snoozed reminder stays quiet
+pub const SNOOZE_MINUTES: i64 = 10;
+
+/// When to be reminded of an appointment
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(untagged)]
+pub enum Alert {
+    /// At a fixed date and time
+    Absolute(PDate),
+    /// Relative to the start of the appointment, saved like `-P1D` for a day before
+    Relative(#[serde(with = "offset")] Duration),
+}
+
+impl Alert {
+    /// When the alert fires for an appointment starting at `start`
+    pub fn due(&self, start: PDate) -> PDate {
+        match self {
+            Alert::Absolute(date) => *date,
+            Alert::Relative(offset) => date::p_date_to_naive_date_time(start)
+                .checked_add_signed(*offset)
+                .map(date::naive_date_time_to_p_date)
+                .unwrap_or(start),
+        }
+    }
+}
+
+impl fmt::Display for Alert {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Alert::Absolute(date) => write!(f, "{} {:02}:{:02}", date.fmt(), date.hour, date.min),
+            Alert::Relative(offset) => write!(f, "{}", date::format_iso_duration(*offset)),
+        }
+    }
+}
+
+mod offset {
+    use chrono::Duration;
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    use crate::data::date;
+
+    pub fn serialize<S: Serializer>(offset: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&date::format_iso_duration(*offset))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        let string = String::deserialize(deserializer)?;
+        date::parse_iso_duration(&string).ok_or(de::Error::custom(format!("invalid duration {}", string)))
+    }
+}
+
+/// An alert that is due, for one occurrence of an appointment
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reminder {
+    pub key: AppointmentKey,
+    /// Date of the occurrence in the unmodified series
+    pub occurrence: PDate,
+    /// When the alert was due
+    pub due: PDate,
+    /// The appointment as it takes place on that occurrence
+    pub appointment: Appointment,
+}
+
+/// A reminder the user dismissed or snoozed, saved so it does not fire again after a restart
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Acknowledged {
+    pub id: i32,
+    /// File of the calendar of the appointment, `None` for the main calendar
+    #[serde(default)]
+    pub calendar: Option<String>,
+    pub occurrence: PDate,
+    pub due: PDate,
+    /// Time the reminder fires again, `None` if it was dismissed
+    #[serde(default)]
+    pub snoozed_until: Option<PDate>,
+}
+
+impl Reminder {
+    fn is(&self, acknowledged: &Acknowledged) -> bool {
+        self.key.id == acknowledged.id && self.key.calendar == acknowledged.calendar
+            && self.occurrence == acknowledged.occurrence && self.due == acknowledged.due
+    }
+
+    pub fn dismiss(&self) -> Acknowledged {
+        self.acknowledge(None)
+    }
+
+    pub fn snooze(&self, now: NaiveDateTime) -> Acknowledged {
+        self.acknowledge(Some(date::naive_date_time_to_p_date(now + Duration::minutes(SNOOZE_MINUTES))))
+    }
+
+    fn acknowledge(&self, snoozed_until: Option<PDate>) -> Acknowledged {
+        let AppointmentKey { calendar, id } = self.key.clone();
+        Acknowledged { id, calendar, occurrence: self.occurrence, due: self.due, snoozed_until }
+    }
+}
+
+/// The reminders of occurrences from today on whose alert time has passed
+///
+/// Dismissed reminders and those snoozed past `now` are left out.
+pub fn due(appointments: &[Appointment], acknowledged: &[Acknowledged], now: NaiveDateTime) -> Vec<Reminder> {
+    let today = now.date();
+    let mut reminders = vec![];
+    // Alerts can also come from the overrides of a series
+    for appointment in appointments.iter().filter(|x| !x.alerts.is_empty() || !x.overrides.is_empty()) {
+        let occurrences = today.iter_days()
+            .take(LOOKAHEAD_DAYS as usize)
+            .flat_map(|day| appointment.occurrences_on(day));
+        for occurrence in occurrences {
+            for alert in &occurrence.appointment.alerts {
+                let due = alert.due(occurrence.appointment.date);
+                if date::p_date_to_naive_date_time(due) > now {
+                    continue
+                }
+                let reminder = Reminder { key: appointment.key(), occurrence: occurrence.original, due, appointment: occurrence.appointment.clone() };
+                let quiet = acknowledged.iter()
+                    .filter(|acknowledged| reminder.is(acknowledged))
+                    .any(|acknowledged| match acknowledged.snoozed_until {
+                        Some(until) => date::p_date_to_naive_date_time(until) > now,
+                        None => true,
+                    });
+                if !quiet {
+                    reminders.push(reminder);
+                }
+            }
+        }
+    }
+    reminders.sort_by_key(|reminder| (reminder.appointment.date.date(), reminder.appointment.sort_key()));
+    reminders
+}
+
+/// Replaces the previous state of the same reminder with `new`
+pub fn acknowledge(acknowledged: &mut Vec<Acknowledged>, new: Acknowledged) {
+    acknowledged.retain(|x| !(x.id == new.id && x.calendar == new.calendar && x.occurrence == new.occurrence && x.due == new.due));
+    acknowledged.push(new);
+}
+
+/// Drops the states of occurrences before yesterday, they can not become due again
+pub fn prune(acknowledged: &mut Vec<Acknowledged>, now: NaiveDateTime) {
+    let yesterday = now.date() - Days::new(1);
+    acknowledged.retain(|x| x.occurrence.date() >= yesterday);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn at(day: u32, hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap().and_hms_opt(hour, 0, 0).unwrap()
+    }
+
+    fn appointment(day: u32, alert: NaiveDateTime) -> Appointment {
+        Appointment {
+            id: 1,
+            date: date::naive_date_time_to_p_date(at(day, 0)),
+            alerts: vec![Alert::Absolute(date::naive_date_time_to_p_date(alert))],
+            ..Appointment::default()
+        }
+    }
+
+    #[test]
+    fn fires_after_alert_time() {
+        let appointments = vec![appointment(20, at(19, 9))];
+        assert!(due(&appointments, &[], at(19, 8)).is_empty());
+        assert_eq!(due(&appointments, &[], at(19, 10)).len(), 1);
+        assert!(due(&appointments, &[], at(21, 10)).is_empty());
+        assert!(due(&[Appointment { date: date::naive_date_time_to_p_date(at(20, 0)), ..Appointment::default() }], &[], at(19, 10)).is_empty());
+    }
+
+    #[test]
+    fn fires_every_alert() {
+        let mut appointments = vec![appointment(20, at(19, 9))];
+        appointments[0].alerts.push(Alert::Relative(Duration::hours(-1)));
+        appointments[0].alerts.push(Alert::Relative(Duration::minutes(-15)));
+        assert_eq!(due(&appointments, &[], at(19, 10)).len(), 1);
+        assert_eq!(due(&appointments, &[], at(19, 23)).len(), 2);
+        let reminders = due(&appointments, &[], at(20, 0));
+        assert_eq!(reminders.len(), 3);
+        assert_eq!(reminders[2].due, PDate::new(2026, 10, 19, 23, 45, 0));
+    }
+
+    #[test]
+    fn alerts_round_trip() {
+        let alerts = vec![Alert::Absolute(PDate::new(2026, 10, 19, 9, 0, 0)), Alert::Relative(Duration::days(-1)), Alert::Relative(Duration::minutes(-90))];
+        let text = serde_yaml::to_string(&alerts).unwrap();
+        assert!(text.contains("-P1D") && text.contains("-PT1H30M"));
+        assert_eq!(serde_yaml::from_str::<Vec<Alert>>(&text).unwrap(), alerts);
+    }
+
+    #[test]
+    fn dismissed_and_snoozed_reminders_stay_quiet() {
+        let appointments = vec![appointment(20, at(19, 9))];
+        let reminder = due(&appointments, &[], at(19, 10)).remove(0);
+
+        assert!(due(&appointments, &[reminder.dismiss()], at(19, 10)).is_empty());
+
+        let snoozed = vec![reminder.snooze(at(19, 10))];
+        assert!(due(&appointments, &snoozed, at(19, 10)).is_empty());
+        assert_eq!(due(&appointments, &snoozed, at(19, 11)).len(), 1);
+
+        // The same id in another calendar is another appointment
+        let other = vec![Appointment { calendar: Some("work.yml".to_string()), ..appointment(20, at(19, 9)) }];
+        assert_eq!(due(&other, &[reminder.dismiss()], at(19, 10)).len(), 1);
+    }
+}
@@ -0,0 +1,200 @@
+use std::fmt;
+
+use chrono::NaiveDate;
+
+use super::{date, date_format, Appointment, Priority};
+
+/// A parsed search query
+///
+/// Words are looked up in the description and the tags, the filters
+/// `tag:`, `priority:`, `before:` and `after:` narrow the results down.
+/// All parts of a query have to match.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Query {
+    pub words: Vec<String>,
+    pub tags: Vec<String>,
+    pub priority: Option<Priority>,
+    /// Last day an appointment may take place on
+    pub before: Option<NaiveDate>,
+    /// First day an appointment may take place on
+    pub after: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueryError {
+    /// A filter without a value, like `tag:`
+    MissingValue(String),
+    UnknownPriority(String),
+    InvalidDate(String),
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QueryError::MissingValue(filter) => write!(f, "{}", t!("query-missing-value", filter = filter)),
+            QueryError::UnknownPriority(priority) => write!(f, "{}", t!("query-unknown-priority", priority = priority)),
+            QueryError::InvalidDate(date) => write!(f, "{}", t!("query-invalid-date", date = date, format = date_format::display_format().placeholder())),
+        }
+    }
+}
+
+impl Query {
+    pub fn parse(query: &str) -> Result<Query, QueryError> {
+        let mut result = Query::default();
+        for part in query.split_whitespace() {
+            let Some((filter, value)) = part.split_once(':') else {
+                result.words.push(part.to_lowercase());
+                continue
+            };
+            let filter = filter.to_lowercase();
+            if !["tag", "priority", "before", "after"].contains(&filter.as_str()) {
+                // Not a filter, like the time in "meeting 10:00"
+                result.words.push(part.to_lowercase());
+                continue
+            }
+            if value.is_empty() {
+                return Err(QueryError::MissingValue(filter))
+            }
+            match filter.as_str() {
+                "tag" => result.tags.push(value.to_lowercase()),
+                "priority" => result.priority = Some(parse_priority(value)?),
+                "before" => result.before = Some(parse_date(value)?),
+                _ => result.after = Some(parse_date(value)?),
+            }
+        }
+        Ok(result)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Query::default()
+    }
+
+    pub fn matches(&self, appointment: &Appointment) -> bool {
+        let description = appointment.description().to_lowercase();
+        let tags: Vec<String> = appointment.tags.iter()
+            .flatten()
+            .map(|tag| tag.to_lowercase())
+            .collect();
+        self.words.iter().all(|word| description.contains(word) || tags.iter().any(|tag| tag.contains(word)))
+            && self.tags.iter().all(|wanted| tags.contains(wanted))
+            && self.priority.is_none_or(|priority| appointment.priority == priority)
+            && self.matches_dates(appointment)
+    }
+
+    /// Whether the appointment, or for series any of its occurrences, lies between `after` and `before`
+    ///
+    /// Modified occurrences count on the day they were moved to, not on the one they replace.
+    fn matches_dates(&self, appointment: &Appointment) -> bool {
+        let in_range = |day: NaiveDate| self.after.is_none_or(|after| day >= after) && self.before.is_none_or(|before| day <= before);
+        let Some(recurrence) = &appointment.recurrence else {
+            return in_range(appointment.date.date())
+        };
+        if appointment.overrides.iter().any(|o| in_range(o.appointment.date.date())) {
+            return true
+        }
+        let replaced = |day: NaiveDate| appointment.exceptions.iter()
+            .chain(appointment.overrides.iter().map(|o| &o.occurrence))
+            .any(|date| date.date() == day);
+        recurrence.occurrences(appointment.date.date())
+            .skip_while(|day| self.after.is_some_and(|after| *day < after))
+            .take_while(|day| self.before.is_none_or(|before| *day <= before))
+            .any(|day| !replaced(day))
+    }
+}
+
+fn parse_priority(value: &str) -> Result<Priority, QueryError> {
+    Priority::from_name(value).ok_or(QueryError::UnknownPriority(value.to_string()))
+}
+
+fn parse_date(value: &str) -> Result<NaiveDate, QueryError> {
+    date_format::parse_date(value, date::now().date()).ok_or(QueryError::InvalidDate(value.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{date, Frequency, Recurrence};
+
+    fn appointment(description: &str, tags: &[&str], priority: Priority, day: NaiveDate) -> Appointment {
+        Appointment {
+            description: description.to_string(),
+            tags: Some(tags.iter().map(|tag| tag.to_string()).collect()),
+            priority,
+            date: date::naive_date_time_to_p_date(day.and_hms_opt(0, 0, 0).unwrap()),
+            ..Appointment::default()
+        }
+    }
+
+    fn day(day: u32, month: u32, year: i32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn parses_words_and_filters() {
+        let query = Query::parse("Review tag:Work priority:high before:31.12.2026 after:1.10.2026").unwrap();
+        assert_eq!(query, Query {
+            words: vec!["review".to_string()],
+            tags: vec!["work".to_string()],
+            priority: Some(Priority::High),
+            before: Some(day(31, 12, 2026)),
+            after: Some(day(1, 10, 2026)),
+        });
+    }
+
+    #[test]
+    fn keeps_unknown_filters_as_words() {
+        let query = Query::parse("call 10:30").unwrap();
+        assert_eq!(query.words, vec!["call", "10:30"]);
+    }
+
+    #[test]
+    fn rejects_invalid_filters() {
+        assert_eq!(Query::parse("tag:"), Err(QueryError::MissingValue("tag".to_string())));
+        assert_eq!(Query::parse("priority:urgent"), Err(QueryError::UnknownPriority("urgent".to_string())));
+        assert_eq!(Query::parse("before:someday"), Err(QueryError::InvalidDate("someday".to_string())));
+    }
+
+    #[test]
+    fn matches_description_and_tags() {
+        let dentist = appointment("Dentist", &["health"], Priority::Middle, day(20, 10, 2026));
+        assert!(Query::parse("dent").unwrap().matches(&dentist));
+        assert!(Query::parse("HEALTH").unwrap().matches(&dentist));
+        assert!(Query::parse("tag:health priority:middle").unwrap().matches(&dentist));
+        assert!(!Query::parse("tag:heal").unwrap().matches(&dentist));
+        assert!(!Query::parse("dentist priority:low").unwrap().matches(&dentist));
+    }
+
+    #[test]
+    fn matches_dates() {
+        let dentist = appointment("Dentist", &[], Priority::Low, day(20, 10, 2026));
+        assert!(Query::parse("after:20.10.2026 before:20.10.2026").unwrap().matches(&dentist));
+        assert!(!Query::parse("after:21.10.2026").unwrap().matches(&dentist));
+        assert!(!Query::parse("before:19.10.2026").unwrap().matches(&dentist));
+    }
+
+    #[test]
+    fn matches_later_occurrences_of_series() {
+        let mut standup = appointment("Standup", &[], Priority::Low, day(5, 1, 2026));
+        standup.recurrence = Some(Recurrence { frequency: Frequency::Weekly, ..Recurrence::default() });
+        assert!(Query::parse("after:1.10.2026 before:31.10.2026").unwrap().matches(&standup));
+        standup.recurrence.as_mut().unwrap().until = Some(date::naive_date_time_to_p_date(day(1, 6, 2026).and_hms_opt(0, 0, 0).unwrap()));
+        assert!(!Query::parse("after:1.10.2026 before:31.10.2026").unwrap().matches(&standup));
+    }
+
+    #[test]
+    fn matches_moved_occurrences_on_their_new_day() {
+        let mut review = appointment("Review", &[], Priority::Low, day(5, 10, 2026));
+        review.recurrence = Some(Recurrence { count: Some(2), ..Recurrence::default() });
+        let moved = review.occurrence(review.date).appointment.moved_to(day(8, 10, 2026));
+        review.override_occurrence(review.date, moved);
+        let on = |review: &Appointment, date: &str| Query::parse(&format!("after:{} before:{}", date, date)).unwrap().matches(review);
+        assert!(!on(&review, "5.10.2026"));
+        assert!(on(&review, "8.10.2026"));
+        assert!(on(&review, "12.10.2026"));
+
+        review.exclude(date::naive_date_time_to_p_date(day(12, 10, 2026).and_hms_opt(0, 0, 0).unwrap()));
+        assert!(!on(&review, "12.10.2026"));
+        assert!(Query::parse("after:6.10.2026").unwrap().matches(&review));
+        assert!(!Query::parse("after:9.10.2026").unwrap().matches(&review));
+    }
+}
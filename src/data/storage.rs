@@ -0,0 +1,275 @@
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use serde::{Deserialize, Serialize};
+
+use super::{Appointment, PDate, Task, calendars, file_path, date, migration};
+use super::reminder::Acknowledged;
+
+/// Number of backups kept next to the data file
+pub const BACKUPS: usize = 5;
+
+#[derive(Debug)]
+pub enum StorageError {
+    Io(io::Error),
+    /// The file exists but its content is not a list of appointments
+    Parse(serde_yaml::Error),
+    /// The file was written by a newer version of Planer
+    UnsupportedVersion(u32),
+    /// Upgrading the file from an older version failed
+    Migration(String),
+}
+
+/// The content of the data file
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SavedData {
+    pub version: u32,
+    #[serde(default)]
+    pub metadata: Metadata,
+    pub appointments: Vec<Appointment>,
+    /// Reminders that were dismissed or snoozed
+    #[serde(default)]
+    pub reminders: Vec<Acknowledged>,
+    #[serde(default)]
+    pub tasks: Vec<Task>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(default)]
+pub struct Metadata {
+    /// Version of Planer that wrote the file
+    pub app_version: String,
+    pub saved_at: Option<PDate>,
+}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StorageError::Io(error) => write!(f, "{}", t!("error-file-access", error = error)),
+            StorageError::Parse(error) => write!(f, "{}", t!("storage-damaged", error = error)),
+            StorageError::UnsupportedVersion(version) => write!(f, "{}", t!("storage-newer-version", version = version)),
+            StorageError::Migration(error) => write!(f, "{}", t!("storage-migration", error = error)),
+        }
+    }
+}
+
+impl From<io::Error> for StorageError {
+    fn from(error: io::Error) -> Self {
+        StorageError::Io(error)
+    }
+}
+
+impl From<serde_yaml::Error> for StorageError {
+    fn from(error: serde_yaml::Error) -> Self {
+        StorageError::Parse(error)
+    }
+}
+
+impl SavedData {
+    /// The data as written by this version of Planer
+    pub fn new(appointments: Vec<Appointment>, reminders: Vec<Acknowledged>, tasks: Vec<Task>) -> Self {
+        SavedData {
+            version: migration::VERSION,
+            metadata: Metadata {
+                app_version: env!("CARGO_PKG_VERSION").to_string(),
+                saved_at: Some(date::naive_date_time_to_p_date(chrono::Local::now().naive_local())),
+            },
+            appointments,
+            reminders,
+            tasks,
+        }
+    }
+}
+
+/// Directory of the data file chosen in the config, `None` for the default
+static DATA_DIR: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+pub fn set_data_dir(dir: Option<PathBuf>) {
+    *DATA_DIR.write().unwrap() = dir;
+}
+
+/// The directory of the data files, chosen in the config or next to it
+pub fn data_dir() -> PathBuf {
+    DATA_DIR.read().unwrap().clone().unwrap_or_else(file_path)
+}
+
+/// The file the appointments of the main calendar are saved in
+pub fn data_file() -> PathBuf {
+    data_dir().join(calendars::MAIN_FILE)
+}
+
+/// Reads the data from `path`, a missing or empty file holds no appointments
+///
+/// Files of older versions are upgraded, a copy of the file as it was before
+/// is kept as `saved.yml.v<version>`.
+pub fn load(path: &Path) -> Result<SavedData, StorageError> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(SavedData::new(vec![], vec![], vec![])),
+        Err(error) => return Err(error.into()),
+    };
+    if text.trim().is_empty() {
+        return Ok(SavedData::new(vec![], vec![], vec![]))
+    }
+    let value: serde_yaml::Value = serde_yaml::from_str(&text)?;
+    let version = migration::version_of(&value)?;
+    if version < migration::VERSION {
+        let copy = with_suffix(path, &format!("v{}", version));
+        if !copy.exists() {
+            fs::copy(path, copy)?;
+        }
+    }
+    Ok(serde_yaml::from_value(migration::migrate(value)?)?)
+}
+
+/// Writes the appointments, reminder states and tasks to `path` after moving its previous content into the backups
+pub fn save(path: &Path, appointments: &[Appointment], reminders: &[Acknowledged], tasks: &[Task]) -> Result<(), StorageError> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let text = serde_yaml::to_string(&SavedData::new(appointments.to_vec(), reminders.to_vec(), tasks.to_vec()))?;
+    rotate_backups(path)?;
+    write_atomically(path, &text)
+}
+
+/// Writes `text` to a temporary file that then replaces `path`
+///
+/// A crash in the middle of writing leaves the old file intact.
+pub fn write_atomically(path: &Path, text: &str) -> Result<(), StorageError> {
+    let temp = with_suffix(path, "tmp");
+    let mut file = File::create(&temp)?;
+    file.write_all(text.as_bytes())?;
+    file.sync_all()?;
+    fs::rename(&temp, path)?;
+    Ok(())
+}
+
+/// The existing backups of `path`, newest first
+pub fn backups(path: &Path) -> Vec<PathBuf> {
+    (1..=BACKUPS)
+        .map(|i| backup_path(path, i))
+        .filter(|backup| backup.exists())
+        .collect()
+}
+
+/// Reads the data of a backup and saves it as the current data
+pub fn restore(backup: &Path, path: &Path) -> Result<SavedData, StorageError> {
+    let data = load(backup)?;
+    save(path, &data.appointments, &data.reminders, &data.tasks)?;
+    Ok(data)
+}
+
+pub fn backup_path(path: &Path, number: usize) -> PathBuf {
+    with_suffix(path, &number.to_string())
+}
+
+/// Shifts `saved.yml.1` to `saved.yml.2` and so on, dropping the oldest, and copies the file to `saved.yml.1`
+fn rotate_backups(path: &Path) -> Result<(), StorageError> {
+    if !path.exists() {
+        return Ok(())
+    }
+    for i in (1..BACKUPS).rev() {
+        let backup = backup_path(path, i);
+        if backup.exists() {
+            fs::rename(&backup, backup_path(path, i + 1))?;
+        }
+    }
+    fs::copy(path, backup_path(path, 1))?;
+    Ok(())
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An empty directory of its own for each test
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("planer-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn appointment(id: i32) -> Appointment {
+        Appointment { id, description: format!("Appointment {}", id), ..Appointment::default() }
+    }
+
+    #[test]
+    fn missing_and_empty_files_hold_nothing() {
+        let dir = temp_dir("empty");
+        let path = dir.join("saved.yml");
+        assert!(load(&path).unwrap().appointments.is_empty());
+        fs::write(&path, "  \n").unwrap();
+        assert!(load(&path).unwrap().appointments.is_empty());
+    }
+
+    #[test]
+    fn rejects_damaged_files() {
+        let dir = temp_dir("damaged");
+        let path = dir.join("saved.yml");
+        fs::write(&path, "version: 2\nappointments: not a list\n").unwrap();
+        assert!(matches!(load(&path), Err(StorageError::Parse(_))));
+        fs::write(&path, "just text").unwrap();
+        assert!(matches!(load(&path), Err(StorageError::Migration(_))));
+        fs::write(&path, "version: 2\nappointments: [").unwrap();
+        assert!(matches!(load(&path), Err(StorageError::Parse(_))));
+    }
+
+    #[test]
+    fn saves_through_a_temporary_file() {
+        let dir = temp_dir("save");
+        let path = dir.join("data").join("saved.yml");
+        // A temporary file left behind by a crash is replaced
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(with_suffix(&path, "tmp"), "half written").unwrap();
+
+        save(&path, &[appointment(1), appointment(2)], &[], &[]).unwrap();
+        assert!(!with_suffix(&path, "tmp").exists());
+        let data = load(&path).unwrap();
+        assert_eq!(data.version, migration::VERSION);
+        assert_eq!(data.appointments, vec![appointment(1), appointment(2)]);
+        // There was nothing to back up
+        assert!(backups(&path).is_empty());
+    }
+
+    #[test]
+    fn keeps_a_limited_number_of_backups() {
+        let dir = temp_dir("rotate");
+        let path = dir.join("saved.yml");
+        for id in 0..BACKUPS as i32 + 3 {
+            save(&path, &[appointment(id)], &[], &[]).unwrap();
+        }
+        let backups = backups(&path);
+        assert_eq!(backups.len(), BACKUPS);
+        assert_eq!(backups[0], backup_path(&path, 1));
+        assert!(!backup_path(&path, BACKUPS + 1).exists());
+        // The newest backup holds the data before the last save, the oldest the one before that many saves
+        assert_eq!(load(&backups[0]).unwrap().appointments, vec![appointment(BACKUPS as i32 + 1)]);
+        assert_eq!(load(&backups[BACKUPS - 1]).unwrap().appointments, vec![appointment(2)]);
+    }
+
+    #[test]
+    fn restores_backups() {
+        let dir = temp_dir("restore");
+        let path = dir.join("saved.yml");
+        assert_eq!(backup_path(&path, 3), dir.join("saved.yml.3"));
+        save(&path, &[appointment(1)], &[], &[]).unwrap();
+        save(&path, &[appointment(2)], &[], &[]).unwrap();
+
+        let data = restore(&backup_path(&path, 1), &path).unwrap();
+        assert_eq!(data.appointments, vec![appointment(1)]);
+        assert_eq!(load(&path).unwrap().appointments, vec![appointment(1)]);
+        // The data that was replaced can be restored in turn
+        assert_eq!(load(&backup_path(&path, 1)).unwrap().appointments, vec![appointment(2)]);
+    }
+}
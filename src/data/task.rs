@@ -0,0 +1,156 @@
+use std::fmt;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use super::{PDate, Priority};
+
+/// Something to be done, optionally by a due date
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct Task {
+    pub id: i32,
+    pub description: String,
+    pub priority: Priority,
+    /// The day the task has to be done by, `None` for someday
+    pub due: Option<PDate>,
+    /// When the task was done, `None` while it is open
+    pub completed: Option<PDate>,
+    pub checklist: Vec<ChecklistItem>,
+    /// UID of the to-do in the iCalendar file it was imported from
+    pub uid: Option<String>,
+}
+
+/// A step of a task
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(default)]
+pub struct ChecklistItem {
+    pub text: String,
+    pub done: bool,
+}
+
+/// Where a task is listed, in the order the groups are shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Group {
+    Overdue,
+    Today,
+    Upcoming,
+    Someday,
+    Done,
+}
+
+impl Group {
+    pub const ALL: &[Self] = &[Self::Overdue, Self::Today, Self::Upcoming, Self::Someday, Self::Done];
+}
+
+impl fmt::Display for Group {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Group::Overdue => t!("task-group-overdue"),
+            Group::Today => t!("task-group-today"),
+            Group::Upcoming => t!("task-group-upcoming"),
+            Group::Someday => t!("task-group-someday"),
+            Group::Done => t!("task-group-done"),
+        }
+        .fmt(f)
+    }
+}
+
+impl Default for Task {
+    fn default() -> Self {
+        Task { id: 0, description: "".to_string(), priority: Priority::Low, due: None, completed: None, checklist: vec![], uid: None }
+    }
+}
+
+impl Task {
+    pub fn is_done(&self) -> bool {
+        self.completed.is_some()
+    }
+
+    /// Marks the task as done at `now` or as open again
+    pub fn set_done(&mut self, done: bool, now: PDate) {
+        if done != self.is_done() {
+            self.completed = done.then_some(now);
+        }
+    }
+
+    /// The number of done checklist items and of all of them
+    pub fn progress(&self) -> (usize, usize) {
+        (self.checklist.iter().filter(|item| item.done).count(), self.checklist.len())
+    }
+
+    /// The description with the progress of the checklist, if there is one
+    pub fn label(&self) -> String {
+        match self.progress() {
+            (_, 0) => self.description.clone(),
+            (done, all) => format!("{} ({}/{})", self.description, done, all),
+        }
+    }
+
+    pub fn group(&self, today: NaiveDate) -> Group {
+        match self.due.map(PDate::date) {
+            _ if self.is_done() => Group::Done,
+            None => Group::Someday,
+            Some(due) if due < today => Group::Overdue,
+            Some(due) if due == today => Group::Today,
+            Some(_) => Group::Upcoming,
+        }
+    }
+
+    /// Key used to order the tasks of a group, the earliest due and most important first
+    pub fn sort_key(&self) -> (Option<NaiveDate>, u8, String) {
+        let priority = match self.priority {
+            Priority::High => 0,
+            Priority::Middle => 1,
+            Priority::Low => 2,
+        };
+        (self.due.map(PDate::date), priority, self.description.to_lowercase())
+    }
+}
+
+/// The tasks due on `day`, done or not
+pub fn due_on(tasks: &[Task], day: NaiveDate) -> Vec<&Task> {
+    tasks.iter()
+        .filter(|task| task.due.is_some_and(|due| due.date() == day))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(due: Option<(i32, u32, u32)>) -> Task {
+        Task { due: due.map(|(year, month, day)| PDate::new(year, month, day, 0, 0, 0)), ..Task::default() }
+    }
+
+    #[test]
+    fn groups_by_due_date() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+        assert_eq!(task(Some((2026, 10, 17))).group(today), Group::Overdue);
+        assert_eq!(task(Some((2026, 10, 18))).group(today), Group::Today);
+        assert_eq!(task(Some((2026, 11, 1))).group(today), Group::Upcoming);
+        assert_eq!(task(None).group(today), Group::Someday);
+
+        let mut done = task(Some((2026, 10, 17)));
+        done.set_done(true, PDate::new(2026, 10, 18, 9, 30, 0));
+        assert_eq!(done.group(today), Group::Done);
+        // Checking a done task again keeps the time it was done
+        done.set_done(true, PDate::new(2026, 10, 19, 8, 0, 0));
+        assert_eq!(done.completed, Some(PDate::new(2026, 10, 18, 9, 30, 0)));
+        done.set_done(false, PDate::new(2026, 10, 19, 8, 0, 0));
+        assert_eq!(done.completed, None);
+    }
+
+    #[test]
+    fn counts_progress() {
+        let mut task = Task { description: "Move".to_string(), ..Task::default() };
+        assert_eq!(task.label(), "Move");
+        task.checklist = vec![
+            ChecklistItem { text: "Boxes".to_string(), done: true },
+            ChecklistItem { text: "Van".to_string(), done: false },
+        ];
+        assert_eq!(task.progress(), (1, 2));
+        assert_eq!(task.label(), "Move (1/2)");
+        assert_eq!(crate::data::new_id(&[Task { id: 0, ..Task::default() }, Task { id: 2, ..Task::default() }]), 1);
+    }
+}
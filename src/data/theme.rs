@@ -0,0 +1,112 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use serde::{Deserialize, Serialize};
+
+use super::file_path;
+use super::storage::StorageError;
+
+/// A palette defined by the user in `themes.yml`
+///
+/// ```yaml
+/// - name: Solarized
+///   background: "#fdf6e3"
+///   text: "#657b83"
+///   primary: "#268bd2"
+///   success: "#859900"
+///   danger: "#dc322f"
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Palette {
+    pub name: String,
+    pub background: Rgb,
+    pub text: Rgb,
+    /// Middle priority, today and the buttons
+    pub primary: Rgb,
+    /// Low priority
+    pub success: Rgb,
+    /// High priority and the current time
+    pub danger: Rgb,
+}
+
+/// A color written as `#rrggbb`
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(try_from = "String", into = "String")]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl TryFrom<String> for Rgb {
+    type Error = String;
+
+    fn try_from(string: String) -> Result<Self, Self::Error> {
+        let hex = string.trim().strip_prefix('#').unwrap_or(string.trim());
+        let channel = |index: usize| hex.get(index..index + 2).and_then(|x| u8::from_str_radix(x, 16).ok());
+        match (hex.len(), channel(0), channel(2), channel(4)) {
+            (6, Some(r), Some(g), Some(b)) => Ok(Rgb(r, g, b)),
+            _ => Err(format!("invalid color {}, use #rrggbb", string)),
+        }
+    }
+}
+
+impl From<Rgb> for String {
+    fn from(rgb: Rgb) -> Self {
+        format!("#{:02x}{:02x}{:02x}", rgb.0, rgb.1, rgb.2)
+    }
+}
+
+/// The file with the palettes of the user, next to the config
+pub fn themes_file() -> PathBuf {
+    file_path().join("themes.yml")
+}
+
+/// Reads the palettes from `path`, a missing file holds none
+pub fn load(path: &Path) -> Result<Vec<Palette>, StorageError> {
+    match fs::read_to_string(path) {
+        Ok(text) if text.trim().is_empty() => Ok(vec![]),
+        Ok(text) => Ok(serde_yaml::from_str(&text)?),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(error) => Err(error.into()),
+    }
+}
+
+/// Whether the desktop prefers dark colors, `false` if that can not be found out
+pub fn system_is_dark() -> bool {
+    let output = |program: &str, args: &[&str]| {
+        Command::new(program).args(args).output()
+            .map(|output| String::from_utf8_lossy(&output.stdout).to_lowercase())
+            .unwrap_or_default()
+    };
+    if cfg!(target_os = "macos") {
+        output("defaults", &["read", "-g", "AppleInterfaceStyle"]).contains("dark")
+    } else if cfg!(target_os = "windows") {
+        output("reg", &["query", r"HKCU\Software\Microsoft\Windows\CurrentVersion\Themes\Personalize", "/v", "AppsUseLightTheme"]).contains("0x0")
+    } else {
+        std::env::var("GTK_THEME").is_ok_and(|theme| theme.to_lowercase().contains("dark"))
+            || output("gsettings", &["get", "org.gnome.desktop.interface", "color-scheme"]).contains("dark")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_colors() {
+        assert_eq!(Rgb::try_from("#fdf6e3".to_string()), Ok(Rgb(0xfd, 0xf6, 0xe3)));
+        assert_eq!(Rgb::try_from("268BD2".to_string()), Ok(Rgb(0x26, 0x8b, 0xd2)));
+        assert!(Rgb::try_from("#fff".to_string()).is_err());
+        assert!(Rgb::try_from("#gggggg".to_string()).is_err());
+        assert_eq!(String::from(Rgb(0, 128, 255)), "#0080ff");
+    }
+
+    #[test]
+    fn reads_palettes() {
+        let text = "- name: Solarized\n  background: \"#fdf6e3\"\n  text: \"#657b83\"\n  primary: \"#268bd2\"\n  success: \"#859900\"\n  danger: \"#dc322f\"\n";
+        let palettes: Vec<Palette> = serde_yaml::from_str(text).unwrap();
+        assert_eq!(palettes.len(), 1);
+        assert_eq!(palettes[0].name, "Solarized");
+        assert_eq!(palettes[0].danger, Rgb(0xdc, 0x32, 0x2f));
+        assert!(serde_yaml::from_str::<Vec<Palette>>(&text.replace("#dc322f", "red")).is_err());
+    }
+}
@@ -0,0 +1,213 @@
+//! Translations of the user interface
+//!
+//! The texts live in Fluent-style catalogs under `locales/`, embedded into the binary.
+//! Each line of a catalog is `key = value`, placeholders are written as `{ $name }`
+//! and lines starting with `#` are comments. Keys missing in a catalog fall back to English.
+
+use std::collections::HashMap;
+use std::env;
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+use chrono::{Datelike, Days, NaiveDate, Weekday};
+
+/// The text of `key` in the current locale, `t!("key", name = value)` fills in `{ $name }`
+macro_rules! t {
+    ($key:expr) => {
+        $crate::i18n::text($key, &[])
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::text($key, &[$((stringify!($name), $value.to_string())),+])
+    };
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    #[default]
+    English,
+    German,
+}
+
+impl Locale {
+    pub const ALL: &[Self] = &[Self::English, Self::German];
+
+    fn source(&self) -> &'static str {
+        match self {
+            Locale::English => include_str!("../locales/en.ftl"),
+            Locale::German => include_str!("../locales/de.ftl"),
+        }
+    }
+
+    /// The locale of a tag like `de_DE.UTF-8` or `en-US`
+    pub fn from_tag(tag: &str) -> Option<Locale> {
+        let language = tag.split(['_', '-', '.', '@']).next()?.to_lowercase();
+        match language.as_str() {
+            "en" => Some(Locale::English),
+            "de" => Some(Locale::German),
+            _ => None,
+        }
+    }
+
+    /// The locale set in the environment, English if it is not supported
+    pub fn detect() -> Locale {
+        ["LC_ALL", "LC_MESSAGES", "LANG"].iter()
+            .filter_map(|name| env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| Locale::from_tag(&value))
+            .unwrap_or_default()
+    }
+
+    /// Day the weeks of the calendar start with
+    pub fn first_weekday(&self) -> Weekday {
+        match self {
+            Locale::English => Weekday::Sun,
+            Locale::German => Weekday::Mon,
+        }
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Locale::English => "English",
+            Locale::German => "Deutsch",
+        }
+        .fmt(f)
+    }
+}
+
+/// Index of the current locale in [`Locale::ALL`]
+static LOCALE: AtomicU8 = AtomicU8::new(0);
+
+pub fn locale() -> Locale {
+    Locale::ALL[LOCALE.load(Ordering::Relaxed) as usize]
+}
+
+pub fn set_locale(locale: Locale) {
+    let index = Locale::ALL.iter().position(|x| *x == locale).unwrap_or(0);
+    LOCALE.store(index as u8, Ordering::Relaxed);
+}
+
+/// The first weekday chosen by the user as days from monday plus one, `0` follows the locale
+static FIRST_WEEKDAY: AtomicU8 = AtomicU8::new(0);
+
+/// Day the weeks of the calendar start with
+pub fn first_weekday() -> Weekday {
+    match FIRST_WEEKDAY.load(Ordering::Relaxed) {
+        0 => locale().first_weekday(),
+        days => Weekday::try_from(days - 1).unwrap_or(Weekday::Mon),
+    }
+}
+
+/// Overrides the first weekday of the locale, `None` goes back to it
+pub fn set_first_weekday(weekday: Option<Weekday>) {
+    let days = weekday.map(|weekday| weekday.num_days_from_monday() as u8 + 1).unwrap_or(0);
+    FIRST_WEEKDAY.store(days, Ordering::Relaxed);
+}
+
+type Catalog = HashMap<&'static str, &'static str>;
+
+fn catalog(locale: Locale) -> &'static Catalog {
+    static CATALOGS: OnceLock<Vec<Catalog>> = OnceLock::new();
+    let catalogs = CATALOGS.get_or_init(|| Locale::ALL.iter().map(|locale| parse_catalog(locale.source())).collect());
+    &catalogs[Locale::ALL.iter().position(|x| *x == locale).unwrap_or(0)]
+}
+
+fn parse_catalog(source: &'static str) -> Catalog {
+    source.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim(), value.trim()))
+        .collect()
+}
+
+/// The text of `key` in the current locale with the placeholders filled in, use [`t!`] instead
+pub fn text(key: &str, args: &[(&str, String)]) -> String {
+    let template = catalog(locale()).get(key)
+        .or(catalog(Locale::English).get(key))
+        .copied()
+        .unwrap_or(key);
+    let mut text = template.to_string();
+    for (name, value) in args {
+        text = text.replace(&format!("{{ ${} }}", name), value);
+    }
+    text
+}
+
+fn weekday_key(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "mon",
+        Weekday::Tue => "tue",
+        Weekday::Wed => "wed",
+        Weekday::Thu => "thu",
+        Weekday::Fri => "fri",
+        Weekday::Sat => "sat",
+        Weekday::Sun => "sun",
+    }
+}
+
+pub fn weekday_name(weekday: Weekday) -> String {
+    text(&format!("weekday-{}", weekday_key(weekday)), &[])
+}
+
+/// Two letter abbreviation of a weekday
+pub fn weekday_short(weekday: Weekday) -> String {
+    text(&format!("weekday-short-{}", weekday_key(weekday)), &[])
+}
+
+/// Name of a month from 1 to 12
+pub fn month_name(month: u32) -> String {
+    text(&format!("month-{}", month), &[])
+}
+
+/// The first day of the week containing `day`, depending on the locale or the user's choice
+pub fn week_start(day: NaiveDate) -> NaiveDate {
+    let first = first_weekday();
+    let offset = (7 + day.weekday().num_days_from_monday() - first.num_days_from_monday()) % 7;
+    day - Days::new(offset as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_catalogs() {
+        let catalog = parse_catalog("# comment\n\ngreeting = Hello { $name }!\n  spaced   =  a = b  \n");
+        assert_eq!(catalog.len(), 2);
+        assert_eq!(catalog["greeting"], "Hello { $name }!");
+        assert_eq!(catalog["spaced"], "a = b");
+    }
+
+    #[test]
+    fn catalogs_have_the_same_keys() {
+        let english = catalog(Locale::English);
+        for locale in Locale::ALL {
+            let catalog = catalog(*locale);
+            let missing: Vec<&&str> = english.keys().filter(|key| !catalog.contains_key(*key)).collect();
+            let unknown: Vec<&&str> = catalog.keys().filter(|key| !english.contains_key(*key)).collect();
+            assert!(missing.is_empty() && unknown.is_empty(), "{:?}: missing {:?}, unknown {:?}", locale, missing, unknown);
+        }
+    }
+
+    #[test]
+    fn detects_locale_from_tags() {
+        assert_eq!(Locale::from_tag("de_DE.UTF-8"), Some(Locale::German));
+        assert_eq!(Locale::from_tag("en-US"), Some(Locale::English));
+        assert_eq!(Locale::from_tag("C"), None);
+        assert_eq!(Locale::from_tag("fr_FR"), None);
+    }
+
+    #[test]
+    fn weeks_start_by_locale() {
+        let sunday = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+        let wednesday = NaiveDate::from_ymd_opt(2026, 10, 21).unwrap();
+        assert_eq!(Locale::English.first_weekday(), Weekday::Sun);
+        assert_eq!(Locale::German.first_weekday(), Weekday::Mon);
+        // The tests run with the default locale
+        assert_eq!(week_start(wednesday), sunday);
+        assert_eq!(week_start(sunday), sunday);
+    }
+}
@@ -1,69 +1,164 @@
-use std::fs::{create_dir, OpenOptions};
+use std::fs::create_dir;
 use iced::{Application, Element, Result, Settings, executor, Theme, Command, Subscription};
 use iced::event::Event;
-use iced::widget::container;
+use iced::widget::{container, column, row, button, text};
 
+#[macro_use]
+mod i18n;
 mod screen;
 mod data;
+mod cli;
 
 use iced_core::Length;
 use screen::calendar::{CalendarWidget, self};
-use data::file_path;
+use screen::{agenda, search, settings, tasks};
+use data::config::{self, Config};
+use data::{file_path, storage};
 
 #[derive(Clone)]
 struct Planer {
     screen: Screen,
+    /// Holds the appointments, the other screens show them and use its dialogs to edit them
+    calendar: CalendarWidget,
+    config: Config,
+    /// Why the config could not be read or saved
+    config_error: Option<String>,
+    /// The config file could not be read, so it is only written once the user agrees to replace it
+    config_unreadable: bool,
+    /// The theme of the config, kept so palettes are not read on every frame
+    theme: Theme,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Message {
     Calendar(calendar::Message),
+    Agenda(agenda::Message),
+    Search(search::Message),
+    Tasks(tasks::Message),
+    Settings(settings::Message),
+    Navigate(Screen),
     Event(Event),
 }
 
-#[derive(Clone)]
+#[derive(Debug, Clone)]
 pub enum Screen {
-    Calendar(calendar::CalendarWidget),
+    Calendar,
+    Agenda(agenda::Agenda),
+    Search(search::Search),
+    Tasks(tasks::Tasks),
+    Settings(settings::Settings),
 }
 
 impl Application for Planer {
     type Executor = executor::Default;
-    type Flags = ();
+    /// The config and why it could not be read
+    type Flags = (Config, Option<String>);
     type Message = Message;
     type Theme = Theme;
 
-    fn new(_flags: ()) -> (Planer, Command<Message>) {
-        (Planer {screen: Screen::Calendar(CalendarWidget::new()) }, Command::none())
+    fn new((config, config_error): (Config, Option<String>)) -> (Planer, Command<Message>) {
+        let calendar = CalendarWidget::new(config.start_view);
+        let config_unreadable = config_error.is_some();
+        let mut planer = Planer { screen: Screen::Calendar, calendar, config, config_error, config_unreadable, theme: Theme::Light };
+        planer.load_theme();
+        (planer, Command::none())
     }
 
     fn title(&self) -> String {
         "Planer".to_string()
     }
 
+    fn theme(&self) -> Theme {
+        self.theme.clone()
+    }
+
     fn update (&mut self, message: Message) -> Command<Message>{
         match message {
             Message::Calendar(message) => {
-                let Screen::Calendar(calendar) = &mut self.screen;
-
-                let command = calendar.update(message);
+                let command = self.calendar.update(message);
 
                 command.map(Message::Calendar)
 
             }
-            Message::Event(event) => {
-                let Screen::Calendar(calendar) = &mut self.screen;
-
-                return calendar.handle_event(event).map(Message::Calendar)
+            Message::Agenda(message) => {
+                let command = match message.clone() {
+                    agenda::Message::EditAppointment(key, original) => self.calendar.update(calendar::Message::EditAppointment(key, original)),
+                    agenda::Message::ContextMenu(key, original) => self.calendar.update(calendar::Message::ContextMenu(key, original)),
+                    _ => Command::none(),
+                };
+                if let Screen::Agenda(agenda) = &mut self.screen {
+                    agenda.update(message);
+                }
+                command.map(Message::Calendar)
+            }
+            Message::Search(message) => {
+                let command = match message.clone() {
+                    search::Message::EditAppointment(key, original) => self.calendar.update(calendar::Message::EditAppointment(key, original)),
+                    search::Message::ContextMenu(key, original) => self.calendar.update(calendar::Message::ContextMenu(key, original)),
+                    _ => Command::none(),
+                };
+                if let Screen::Search(search) = &mut self.screen {
+                    search.update(message);
+                }
+                command.map(Message::Calendar)
             }
+            Message::Tasks(message) => {
+                let command = match message {
+                    tasks::Message::AddTask => self.calendar.update(calendar::Message::AddTask),
+                    tasks::Message::EditTask(id) => self.calendar.update(calendar::Message::EditTask(id)),
+                    tasks::Message::ToggleTask(id, done) => self.calendar.update(calendar::Message::ToggleTask(id, done)),
+                    tasks::Message::ToggleChecklistItem(id, index, done) => self.calendar.update(calendar::Message::ToggleChecklistItem(id, index, done)),
+                    _ => Command::none(),
+                };
+                if let Screen::Tasks(tasks) = &mut self.screen {
+                    tasks.update(message);
+                }
+                command.map(Message::Calendar)
+            }
+            Message::Settings(settings::Message::OverwriteConfig) => {
+                self.config_unreadable = false;
+                self.save_config();
+                Command::none()
+            }
+            Message::Settings(message) => {
+                if let Screen::Settings(settings) = &mut self.screen {
+                    let before = self.config.clone();
+                    settings.update(message, &mut self.config);
+                    if self.config != before {
+                        self.apply_config(&before);
+                    }
+                }
+                Command::none()
+            }
+            Message::Navigate(screen) => {
+                self.screen = screen;
+                Command::none()
+            }
+            Message::Event(event) if self.forwards(&event) => {
+                self.calendar.handle_event(event).map(Message::Calendar)
+            }
+            Message::Event(_) => Command::none(),
         }
     }
 
-    fn view(&self) -> Element<Message> {
+    fn view(&self) -> Element<'_, Message> {
         let content = match &self.screen {
-            Screen::Calendar(calendar) => calendar.view().map(Message::Calendar)
+            Screen::Calendar => self.calendar.view().map(Message::Calendar),
+            Screen::Agenda(agenda) => self.calendar.with_dialog(agenda.view(&self.calendar.appointments()).map(Message::Agenda), Message::Calendar),
+            Screen::Search(search) => self.calendar.with_dialog(search.view(&self.calendar.appointments()).map(Message::Search), Message::Calendar),
+            Screen::Tasks(tasks) => self.calendar.with_dialog(tasks.view(self.calendar.tasks()).map(Message::Tasks), Message::Calendar),
+            Screen::Settings(settings) => settings.view(&self.config, self.config_error.as_deref(), self.config_unreadable).map(Message::Settings),
         };
-
-        container(content)
+        let navigation = row![
+            button(text(t!("nav-calendar"))).on_press(Message::Navigate(Screen::Calendar)),
+            button(text(t!("nav-agenda"))).on_press(Message::Navigate(Screen::Agenda(agenda::Agenda::default()))),
+            button(text(t!("nav-search"))).on_press(Message::Navigate(Screen::Search(search::Search::default()))),
+            button(text(t!("nav-tasks"))).on_press(Message::Navigate(Screen::Tasks(tasks::Tasks::default()))),
+            button(text(t!("nav-settings"))).on_press(Message::Navigate(Screen::Settings(settings::Settings::new(&self.config)))),
+        ]
+        .spacing(5);
+
+        container(column![navigation, self.calendar.view_reminders().map(Message::Calendar), content].spacing(5))
             .height(Length::Fill)
             .width(Length::Fill)
             .into()
@@ -71,26 +166,82 @@ impl Application for Planer {
 
     fn subscription(&self) -> Subscription<Self::Message> {
         let subs: Vec<iced::Subscription<Self::Message>> =
-            vec![iced::subscription::events().map(|e| Message::Event(e))];
+            vec![iced::subscription::events_with(|event, status| match (&event, status) {
+                // Events used by a widget, like typing into a text input, are not shortcuts
+                (Event::Keyboard(iced::keyboard::Event::ModifiersChanged(_)), _) | (_, iced::event::Status::Ignored) => {
+                    Some(Message::Event(event))
+                }
+                _ => None,
+            }),
+            // Warnings are checked against the clock twice a minute
+            iced::time::every(std::time::Duration::from_secs(30))
+                .map(|_| Message::Calendar(calendar::Message::CheckReminders))];
 
             iced::subscription::Subscription::batch(subs)
     }
 }
 
 impl Planer {
-    
+    /// Makes the changed config take effect and saves it, unless the file could not be read
+    fn apply_config(&mut self, before: &Config) {
+        self.config.apply();
+        if self.config.data_dir != before.data_dir {
+            self.calendar.change_data_file();
+        }
+        if !self.config_unreadable {
+            self.save_config();
+        }
+        if self.config.theme != before.theme {
+            self.load_theme();
+        }
+    }
+
+    fn save_config(&mut self) {
+        self.config_error = config::save(&config::config_file(), &self.config)
+            .err()
+            .map(|error| t!("settings-save-failed", error = error));
+    }
+
+    /// Whether the calendar handles `event` while the current screen is shown
+    ///
+    /// Scrolling and the arrow keys move through the calendar, so they only apply to it.
+    /// Quick add, undo and redo need its dialogs, which the settings do not show.
+    fn forwards(&self, event: &Event) -> bool {
+        use iced::keyboard::{Event::KeyPressed, KeyCode};
+        match (&self.screen, event) {
+            (Screen::Calendar, _) => true,
+            (_, Event::Mouse(iced::mouse::Event::WheelScrolled { .. })) => false,
+            (_, Event::Keyboard(KeyPressed { key_code: KeyCode::Up | KeyCode::Down | KeyCode::Left | KeyCode::Right, .. })) => false,
+            (Screen::Settings(_), Event::Keyboard(KeyPressed { key_code: KeyCode::N | KeyCode::Z, .. })) => false,
+            _ => true,
+        }
+    }
+
+    /// Looks up the theme of the config, falling back to the light one if its palette can not be used
+    fn load_theme(&mut self) {
+        match settings::theme(&self.config.theme) {
+            Ok(theme) => self.theme = theme,
+            Err(error) => {
+                self.theme = Theme::Light;
+                self.config_error = Some(error);
+            }
+        }
+    }
 }
 
 fn main() -> Result {
     let _ = create_dir(file_path());
-    let mut path = file_path();
-    path.push("saved.yml");
-    let _file = OpenOptions::new()
-        .create(true)  
-        .write(true)
-        .open(path);
-    // let _appointments = YamlVec { data: vec![Appointment::default()]};
-    // save_appointments(appointments);
-    // let _saved = read_appointments();
-    Planer::run(Settings {..Settings::default()})
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (config, config_error) = match config::load(&config::config_file()) {
+        Ok(config) => (config, None),
+        Err(error) => (Config::default(), Some(error)),
+    };
+    // Commands only follow the data directory, their output stays the same for scripts
+    storage::set_data_dir(config.data_dir.clone());
+    if let Some(code) = cli::run(&args) {
+        std::process::exit(code)
+    }
+    config.apply();
+    let config_error = config_error.map(|error| t!("settings-load-failed", error = error));
+    Planer::run(Settings::with_flags((config, config_error)))
 }
\ No newline at end of file
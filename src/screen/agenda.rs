@@ -0,0 +1,183 @@
+use std::fmt;
+
+use chrono::{Days, NaiveDate};
+use iced::widget::{button, column, container, mouse_area, row, scrollable, text, PickList, Space};
+use iced::{theme, Element, Length};
+
+use crate::data::{date, date_format, Appointment, AppointmentKey, Occurrence, PDate, Priority};
+
+/// Number of days listed by the agenda
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Range {
+    #[default]
+    Week,
+    Month,
+    Quarter,
+}
+
+impl Range {
+    pub const ALL: &[Self] = &[Self::Week, Self::Month, Self::Quarter];
+
+    pub const fn days(&self) -> u64 {
+        match self {
+            Range::Week => 7,
+            Range::Month => 30,
+            Range::Quarter => 90,
+        }
+    }
+}
+
+impl fmt::Display for Range {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", t!("agenda-range", days = self.days()))
+    }
+}
+
+/// Chronological list of the appointments around today
+#[derive(Debug, Clone, Default)]
+pub struct Agenda {
+    range: Range,
+    /// Whether the days of the range before today are listed
+    show_past: bool,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Range(Range),
+    TogglePast,
+    EditAppointment(AppointmentKey, PDate),
+    ContextMenu(AppointmentKey, PDate),
+}
+
+impl Agenda {
+    pub fn update(&mut self, message: Message) {
+        match message {
+            Message::Range(range) => self.range = range,
+            Message::TogglePast => self.show_past = !self.show_past,
+            // Opening the dialogs is done by the calendar
+            Message::EditAppointment(..) | Message::ContextMenu(..) => {}
+        }
+    }
+
+    pub fn view<'a>(&self, appointments: &[Appointment]) -> Element<'a, Message> {
+        let today = date::now().date();
+        let past = days(appointments, today - Days::new(self.range.days()), today);
+        let upcoming = days(appointments, today, today + Days::new(self.range.days()));
+
+        let past_count: usize = past.iter().map(|(_, occurrences)| occurrences.len()).sum();
+        let mut list = column![
+            button(text(if self.show_past { t!("agenda-hide-past", count = past_count) } else { t!("agenda-show-past", count = past_count) }))
+                .style(theme::Button::Text)
+                .on_press(Message::TogglePast),
+        ]
+        .spacing(10);
+        if self.show_past {
+            for (day, occurrences) in past {
+                list = list.push(view_day(day, occurrences, today));
+            }
+        }
+        if upcoming.is_empty() {
+            list = list.push(text(t!("agenda-empty")));
+        }
+        for (day, occurrences) in upcoming {
+            list = list.push(view_day(day, occurrences, today));
+        }
+
+        column![
+            row![
+                text(t!("agenda-title")).size(20),
+                Space::with_width(Length::Fill),
+                PickList::new(Range::ALL, Some(self.range), Message::Range),
+            ]
+            .spacing(10),
+            scrollable(list).height(Length::Fill),
+        ]
+        .spacing(10)
+        .padding(5)
+        .into()
+    }
+}
+
+/// The days from `from` up to `to` (excluded) having appointments, with their sorted occurrences
+fn days(appointments: &[Appointment], from: NaiveDate, to: NaiveDate) -> Vec<(NaiveDate, Vec<Occurrence>)> {
+    from.iter_days()
+        .take_while(|day| *day < to)
+        .map(|day| {
+            let mut occurrences: Vec<Occurrence> = appointments.iter()
+                .flat_map(|appointment| appointment.occurrences_on(day))
+                .collect();
+            occurrences.sort_by_key(|occurrence| occurrence.appointment.sort_key());
+            (day, occurrences)
+        })
+        .filter(|(_, occurrences)| !occurrences.is_empty())
+        .collect()
+}
+
+fn view_day<'a>(day: NaiveDate, occurrences: Vec<Occurrence>, today: NaiveDate) -> Element<'a, Message> {
+    let heading = match (day - today).num_days() {
+        0 => t!("agenda-today", date = date_format::format_date(day)),
+        1 => t!("agenda-tomorrow", date = date_format::format_date(day)),
+        _ => date_format::format_long(day),
+    };
+    let mut content = column![text(heading).size(18)].spacing(5);
+    for occurrence in occurrences {
+        content = content.push(view_occurrence(occurrence));
+    }
+    content.into()
+}
+
+fn view_occurrence<'a>(occurrence: Occurrence) -> Element<'a, Message> {
+    let Occurrence { appointment, original } = occurrence;
+    let mut badges = row![badge(appointment.priority.to_string(), Badge::Priority(appointment.priority))].spacing(5);
+    for tag in appointment.tags.iter().flatten().filter(|tag| !tag.is_empty()) {
+        badges = badges.push(badge(tag.clone(), Badge::Tag));
+    }
+    mouse_area(
+        button(
+            row![
+                text(appointment.time_string()).width(110),
+                text(appointment.description()).width(Length::Fill),
+                badges,
+            ]
+            .spacing(10)
+        )
+        .width(Length::Fill)
+        .style(theme::Button::Secondary)
+        .on_press(Message::EditAppointment(appointment.key(), original))
+    )
+    .on_right_press(Message::ContextMenu(appointment.key(), original))
+    .into()
+}
+
+fn badge<'a>(label: String, style: Badge) -> Element<'a, Message> {
+    container(text(label).size(12))
+        .padding([2, 6])
+        .style(theme::Container::Custom(Box::new(style)))
+        .into()
+}
+
+/// A small label next to an appointment
+enum Badge {
+    Priority(Priority),
+    Tag,
+}
+
+impl container::StyleSheet for Badge {
+    type Style = theme::Theme;
+
+    fn appearance(&self, style: &Self::Style) -> container::Appearance {
+        let palette = style.extended_palette();
+        let pair = match self {
+            Badge::Priority(Priority::High) => palette.danger.base,
+            Badge::Priority(Priority::Middle) => palette.primary.base,
+            Badge::Priority(Priority::Low) => palette.success.base,
+            Badge::Tag => palette.background.strong,
+        };
+        container::Appearance {
+            text_color: Some(pair.text),
+            background: Some(pair.color.into()),
+            border_radius: 8.0.into(),
+            ..Default::default()
+        }
+    }
+}
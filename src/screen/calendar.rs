@@ -1,31 +1,39 @@
 use std::fmt::Debug;
+use std::path::PathBuf;
 
 use chrono::naive::{NaiveDateTime, Days};
 use chrono::naive::NaiveDate;
-use chrono::{Datelike, Months};
-use iced::widget::{Text, text, button, container, text_input, PickList, Space, self, scrollable, Row};
-use iced::widget::{column, button::Button, Container, row, button::Appearance};
+use chrono::{Datelike, Months, NaiveTime, Timelike, Duration};
+use iced::widget::{Text, text, button, container, text_input, PickList, Space, self, scrollable, checkbox, tooltip};
+use iced::widget::{column, button::Button, row, button::Appearance, mouse_area};
 use iced::{Element, Length, Command, theme, window};
-use iced_core::{Widget, Vector};
+use iced_core::Vector;
 use iced_core::keyboard::{KeyCode, Modifiers};
 use iced_core::mouse::ScrollDelta;
 use iced_core::alignment::Horizontal;
 
-use crate::data::{Appointment, read_appointments, save_appointments, Priority};
+use crate::data::{Appointment, AppointmentKey, Priority, Occurrence, EditScope, Task, new_id, tag_counts};
+use crate::data::{Recurrence, Frequency, ByDay, Alert};
 use crate::screen::modal_overlay::Modal;
-use crate::data::{PDate, date};
+use crate::screen::stack::Stack;
+use crate::screen::drag::{Draggable, DropTarget};
+use crate::data::date_format;
+use crate::data::config::StartView;
+use crate::data::calendars::{self, Calendar};
+use crate::data::{PDate, date, ical, file_path, storage};
+use crate::data::history::{History, Change};
+use crate::data::reminder::{self, Reminder, Acknowledged};
+use crate::data::quick_add;
+use crate::data::task::{self, ChecklistItem};
+use crate::i18n;
 
 
-#[derive(Clone)]
-pub struct Calendar {
-    pub active_date: NaiveDateTime,
-}
-
 #[derive(Clone, Debug)]
 enum Depth {
     Year = 0,
     Month = 1,
     Week = 2,
+    Day = 3,
 }
 
 impl Depth {
@@ -34,7 +42,8 @@ impl Depth {
         match *self {
             Depth::Year => Depth::Month,
             Depth::Month => Depth::Week,
-            Depth::Week => Depth::Week,
+            Depth::Week => Depth::Day,
+            Depth::Day => Depth::Day,
         }
     }
 
@@ -42,7 +51,19 @@ impl Depth {
         match *self {
             Depth::Year => Depth::Year,
             Depth::Month => Depth::Year,
-            Depth::Week => Depth::Month,         
+            Depth::Week => Depth::Month,
+            Depth::Day => Depth::Week,
+        }
+    }
+}
+
+impl From<StartView> for Depth {
+    fn from(view: StartView) -> Self {
+        match view {
+            StartView::Year => Depth::Year,
+            StartView::Month => Depth::Month,
+            StartView::Week => Depth::Week,
+            StartView::Day => Depth::Day,
         }
     }
 }
@@ -52,37 +73,210 @@ pub struct CalendarWidget {
     active_date: NaiveDateTime,
     depth: Depth,
     appointments: Vec<Appointment>,
+    tasks: Vec<Task>,
     edit_dialog: Option<DialogOption>,
     dialog_appointment: DialogAppointment,
+    dialog_task: DialogTask,
+    /// Edited occurrence of a recurring series waiting for the user to pick an [`EditScope`]
+    pending_edit: Option<Appointment>,
     modifiers: Modifiers,
+    /// File used by the iCalendar import and export
+    ics_path: String,
+    /// Result of the last action, shown in the toolbar
+    status: String,
+    /// Why the saved appointments could not be loaded, nothing is saved while this is set
+    load_error: Option<String>,
+    /// Target of the "Move to date" dialog
+    move_date: String,
+    history: History,
+    /// Appointments with one of these tags are not shown
+    hidden_tags: Vec<String>,
+    show_tags: bool,
+    /// Tag being renamed and its new name
+    tag_rename: Option<(String, String)>,
+    /// Dismissed and snoozed reminders, saved with the appointments
+    acknowledged: Vec<Acknowledged>,
+    /// Reminders currently shown in the banner
+    reminders: Vec<Reminder>,
+    /// Text of the quick-add bar while it is open
+    quick_add: Option<String>,
+    /// The appointment being dragged to another day or time
+    drag: Option<Drag>,
+    /// The main calendar first, the appointments name the calendar they belong to
+    calendars: Vec<Calendar>,
+    show_calendars: bool,
+    /// Name typed for a new calendar
+    new_calendar: String,
 }
 
 #[derive(Clone)]
 pub struct DialogAppointment {
     date: String,
+    all_day: bool,
+    start: String,
+    end: String,
     priority: Priority,
-    warning: String,
+    /// One row per alert, a date with an optional `hh:mm` or an offset like `-P1D`
+    alerts: Vec<String>,
     tags: String,
     description: String,
+    repeat: bool,
+    frequency: Frequency,
+    interval: String,
+    by_day: String,
+    count: String,
+    until: String,
+    /// File of the calendar, `None` for the main calendar
+    calendar: Option<String>,
 }
 
 impl Default for DialogAppointment {
     fn default() -> Self {
-        DialogAppointment { date: "".to_string(), priority: Priority::Low, warning: "".to_string(), tags: "".to_string(), description: "".to_string() }
+        DialogAppointment { date: "".to_string(), all_day: true, start: "".to_string(), end: "".to_string(), priority: Priority::Low, alerts: vec![], tags: "".to_string(), description: "".to_string(), 
+            repeat: false, frequency: Frequency::Weekly, interval: "1".to_string(), by_day: "".to_string(), count: "".to_string(), until: "".to_string(), calendar: None }
     }
 }
 
 impl DialogAppointment {
     fn from_appointment(appointment: Appointment) -> Self {
-        let tags = appointment.tags.unwrap().join(", ");
-        DialogAppointment { date: appointment.date.fmt(), priority: appointment.priority, warning: appointment.warning.fmt(), tags, description: appointment.description }
+        let tags = appointment.tags.clone().unwrap_or_default().join(", ");
+        let start = appointment.start_time().map(|time| time.format("%H:%M").to_string()).unwrap_or_default();
+        let end = appointment.end_time().map(|time| time.format("%H:%M").to_string()).unwrap_or_default();
+        let recurrence = appointment.recurrence.clone().unwrap_or_default();
+        DialogAppointment { 
+            date: appointment.date.fmt(), 
+            all_day: appointment.all_day, 
+            start, 
+            end, 
+            priority: appointment.priority, 
+            alerts: appointment.alerts.iter().map(Alert::to_string).collect(),
+            tags, 
+            description: appointment.description,
+            repeat: appointment.recurrence.is_some(),
+            frequency: recurrence.frequency,
+            interval: recurrence.interval.to_string(),
+            by_day: recurrence.by_day.iter().map(ByDay::to_string).collect::<Vec<String>>().join(", "),
+            count: recurrence.count.map(|count| count.to_string()).unwrap_or_default(),
+            until: recurrence.until.map(PDate::fmt).unwrap_or_default(),
+            calendar: appointment.calendar,
+        }
+    }
+
+    /// Builds an appointment with the given id from the dialog fields
+    fn to_appointment(&self, id: i32) -> Result<Appointment, String> {
+        let date = valid_date(self.date.clone())?;
+        let (start, end) = if self.all_day {
+            (NaiveTime::MIN, NaiveTime::MIN)
+        } else {
+            let start = valid_time(self.start.clone())?;
+            (start, valid_end(start, self.end.clone())?)
+        };
+        Ok(Appointment {
+            id,
+            date: PDate { hour: start.hour(), min: start.minute(), ..date },
+            end: PDate { hour: end.hour(), min: end.minute(), ..date },
+            all_day: self.all_day,
+            priority: self.priority,
+            alerts: self.alerts.iter()
+                .filter(|alert| !alert.trim().is_empty())
+                .map(|alert| valid_alert(alert.clone()))
+                .collect::<Result<Vec<Alert>, String>>()?,
+            tags: Some(valid_tags(self.tags.clone())?),
+            description: self.description.clone(),
+            recurrence: self.to_recurrence()?,
+            exceptions: vec![],
+            overrides: vec![],
+            calendar: self.calendar.clone(),
+            uid: None,
+        })
+    }
+
+    /// Builds the recurrence rule from the dialog fields, `None` if the appointment does not repeat
+    fn to_recurrence(&self) -> Result<Option<Recurrence>, String> {
+        if !self.repeat {
+            return Ok(None)
+        }
+        let interval = match self.interval.trim().parse::<u32>() {
+            Ok(interval) if interval > 0 => interval,
+            _ => return Err(self.interval.clone())
+        };
+        let by_day = self.by_day.split(',')
+            .filter(|day| !day.trim().is_empty())
+            .map(|day| ByDay::try_from(day.to_string()))
+            .collect::<Result<Vec<ByDay>, String>>()?;
+        let count = match self.count.trim() {
+            "" => None,
+            count => Some(count.parse::<u32>().map_err(|_| self.count.clone())?)
+        };
+        let until = match self.until.trim() {
+            "" => None,
+            until => Some(valid_date(until.to_string())?)
+        };
+        Ok(Some(Recurrence { frequency: self.frequency, interval, by_day, count, until }))
+    }
+}
+
+#[derive(Clone)]
+pub struct DialogTask {
+    description: String,
+    /// Empty for a task without due date
+    due: String,
+    priority: Priority,
+    checklist: Vec<ChecklistItem>,
+}
+
+impl DialogTask {
+    fn from_task(task: &Task) -> Self {
+        DialogTask {
+            description: task.description.clone(),
+            due: task.due.map(PDate::fmt).unwrap_or_default(),
+            priority: task.priority,
+            checklist: task.checklist.clone(),
+        }
+    }
+
+    /// Builds the task from the dialog fields, `task` holds what the dialog does not edit
+    fn to_task(&self, task: Task) -> Result<Task, String> {
+        let due = match self.due.trim() {
+            "" => None,
+            due => Some(valid_date(due.to_string())?),
+        };
+        Ok(Task {
+            description: self.description.clone(),
+            due,
+            priority: self.priority,
+            checklist: self.checklist.iter().filter(|item| !item.text.trim().is_empty()).cloned().collect(),
+            ..task
+        })
     }
 }
 
+/// Where a dragged appointment can be dropped
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DropSlot {
+    /// A day of the month view or the all-day row of the week view, keeping the time
+    Day(NaiveDate),
+    /// An hour in the timeline of a day
+    Hour(NaiveDate, u32),
+}
+
+#[derive(Clone)]
+struct Drag {
+    occurrence: Occurrence,
+    /// The slot under the cursor, where the preview is shown
+    over: Option<DropSlot>,
+}
+
 #[derive(PartialEq, Clone)]
 enum DialogOption {
-    Edit(Appointment),
-    Add(NaiveDateTime)
+    /// Key of the edited appointment and date of the occurrence in its series
+    Edit(AppointmentKey, PDate),
+    Add(NaiveDateTime),
+    ContextMenu(AppointmentKey, PDate),
+    ConfirmDelete(AppointmentKey, PDate),
+    Move(AppointmentKey, PDate),
+    /// The task dialog for the task with this id, `None` for a new task
+    Task(Option<i32>),
 }
 
 #[derive(Debug, Clone)]
@@ -90,33 +284,200 @@ pub enum Message {
     TimeIncrement,
     TimeDecrement,
     AddAppointment(NaiveDateTime),
-    EditAppointment(i32),
+    EditAppointment(AppointmentKey, PDate),
     DialogPriority(Priority),
     DialogDate(String),
-    DialogWarning(String),
+    DialogAllDay(bool),
+    DialogStart(String),
+    DialogEnd(String),
+    DialogAlert(usize, String),
+    DialogAddAlert,
+    DialogRemoveAlert(usize),
     DialogTags(String),
     DialogDescription(String),
+    DialogRepeat(bool),
+    DialogFrequency(Frequency),
+    DialogInterval(String),
+    DialogByDay(String),
+    DialogCount(String),
+    DialogUntil(String),
     DialogCancel,
-    DialogSubmit(Option<Appointment>),
+    DialogSubmit,
+    DialogScope(EditScope),
+    ContextMenu(AppointmentKey, PDate),
+    DeleteAppointment(AppointmentKey, PDate),
+    ConfirmDelete(EditScope),
+    Undo,
+    Redo,
+    DuplicateAppointment(AppointmentKey, PDate),
+    MoveAppointment(AppointmentKey, PDate),
+    MoveDate(String),
+    ConfirmMove,
+    RestoreBackup(PathBuf),
+    DiscardSaved,
+    IcsPath(String),
+    ImportIcs,
+    ExportIcs,
+    JumpTo(NaiveDateTime),
+    DialogTagSuggestion(String),
+    ToggleTags,
+    TagVisible(String, bool),
+    RenameTag(String),
+    TagNewName(String),
+    ConfirmRenameTag,
+    CancelRenameTag,
+    CheckReminders,
+    /// Snoozes the reminder of this appointment, occurrence and due time
+    SnoozeReminder(AppointmentKey, PDate, PDate),
+    DismissReminder(AppointmentKey, PDate, PDate),
+    QuickAdd,
+    QuickAddInput(String),
+    QuickAddSubmit,
+    QuickAddDialog,
+    DragAppointment(AppointmentKey, PDate),
+    DragOver(DropSlot),
+    DropAppointment(DropSlot),
+    AddTask,
+    EditTask(i32),
+    DeleteTask(i32),
+    ToggleTask(i32, bool),
+    ToggleChecklistItem(i32, usize, bool),
+    TaskDescription(String),
+    TaskDue(String),
+    TaskPriority(Priority),
+    TaskItem(usize, String),
+    TaskItemDone(usize, bool),
+    TaskAddItem,
+    TaskRemoveItem(usize),
+    TaskSubmit,
+    DialogCalendar(Calendar),
+    ToggleCalendars,
+    CalendarVisible(String, bool),
+    CalendarReadOnly(String, bool),
+    NewCalendarName(String),
+    AddCalendar,
 }
 
 impl CalendarWidget{
 
-    pub fn new() -> Self {
-        CalendarWidget { 
+    pub fn new(start_view: StartView) -> Self {
+        let (data, load_error) = load();
+        let mut appointments = data.appointments;
+        let (calendars, calendars_error) = load_calendars(&mut appointments);
+        let mut calendar = CalendarWidget { 
             active_date: date::now(), 
-            depth: Depth::Month,
-            appointments: read_appointments(), 
+            depth: start_view.into(),
+            appointments, 
+            tasks: data.tasks,
             edit_dialog: None, 
             dialog_appointment: DialogAppointment::default(), 
-            modifiers: Modifiers::empty() 
+            dialog_task: DialogTask::from_task(&Task::default()),
+            pending_edit: None,
+            modifiers: Modifiers::empty(),
+            ics_path: file_path().join("planer.ics").to_string_lossy().to_string(),
+            status: calendars_error.unwrap_or_default(),
+            load_error,
+            move_date: "".to_string(),
+            history: History::default(),
+            hidden_tags: vec![],
+            show_tags: false,
+            tag_rename: None,
+            acknowledged: data.reminders,
+            reminders: vec![],
+            quick_add: None,
+            drag: None,
+            calendars,
+            show_calendars: false,
+            new_calendar: "".to_string(),
+        };
+        calendar.check_reminders();
+        calendar
+    }
+
+    /// Continues with the data file set in the config, it gets the current appointments if it does not exist yet
+    pub fn change_data_file(&mut self) {
+        if storage::data_file().exists() {
+            let (data, load_error) = load();
+            self.appointments = data.appointments;
+            let (calendars, calendars_error) = load_calendars(&mut self.appointments);
+            self.calendars = calendars;
+            self.status = calendars_error.unwrap_or_default();
+            self.tasks = data.tasks;
+            self.acknowledged = data.reminders;
+            self.load_error = load_error;
+            self.history = History::default();
+            self.edit_dialog = None;
+        } else {
+            self.save();
+            self.save_calendars();
+        }
+        self.check_reminders();
+    }
+
+    fn check_reminders(&mut self) {
+        let now = chrono::Local::now().naive_local();
+        reminder::prune(&mut self.acknowledged, now);
+        self.reminders = reminder::due(&self.appointments, &self.acknowledged, now);
+    }
+
+    /// Records the change from `before` to the current appointments in the history and saves them
+    fn commit(&mut self, label: String, before: Vec<Appointment>) {
+        if let Some(change) = Change::between(label, &before, &self.appointments) {
+            self.history.push(change);
+        }
+        self.check_reminders();
+        self.save();
+    }
+
+    /// Records the change from `before` to the current tasks in the history and saves them
+    fn commit_tasks(&mut self, label: String, before: Vec<Task>) {
+        if let Some(change) = Change::between_tasks(label, &before, &self.tasks) {
+            self.history.push(change);
+        }
+        self.save();
+    }
+
+    /// Saves the appointments, unless loading them failed and the user has not decided what to do yet
+    fn save(&mut self) {
+        if self.load_error.is_some() {
+            return
+        }
+        if let Err(error) = calendars::save_appointments(&self.calendars, &self.appointments, &self.acknowledged, &self.tasks) {
+            self.status = t!("status-save-failed", error = error);
         }
     }
 
+    /// Saves the list of calendars with their settings
+    fn save_calendars(&mut self) {
+        if let Err(error) = calendars::save(&calendars::calendars_file(), &self.calendars) {
+            self.status = t!("calendars-save-failed", error = error);
+        }
+    }
+
+    /// The calendar `appointment` belongs to
+    fn calendar_of(&self, appointment: &Appointment) -> Option<&Calendar> {
+        calendars::calendar_of(&self.calendars, appointment)
+    }
+
+    /// Whether the appointment is in a calendar that can not be changed
+    fn is_read_only(&self, key: &AppointmentKey) -> bool {
+        self.appointments.iter()
+            .find(|x| x.key() == *key)
+            .and_then(|appointment| self.calendar_of(appointment))
+            .is_some_and(|calendar| !calendar.is_writable())
+    }
+
+    /// An id for a new appointment of `calendar`, ids only have to be unique within a calendar
+    fn new_id_in(&self, calendar: &Option<String>) -> i32 {
+        new_id(self.appointments.iter().filter(|x| x.calendar == *calendar))
+    }
+
     pub fn update(&mut self, message: Message) -> Command<Message>{
         match message {
             Message::TimeIncrement => {
                 self. active_date = match self.depth {
+                    Depth::Day => self.active_date.checked_add_days(Days::new(1))
+                    .unwrap_or(self.active_date),
                     Depth::Week => self.active_date.checked_add_days(Days::new(7))
                     .unwrap_or(self.active_date),
                     Depth::Month => self.active_date.checked_add_months(Months::new(1))
@@ -128,6 +489,8 @@ impl CalendarWidget{
             }
             Message::TimeDecrement => {
                 self. active_date = match self.depth {
+                    Depth::Day => self.active_date.checked_sub_days(Days::new(1))
+                    .unwrap_or(self.active_date),
                     Depth::Week => self.active_date.checked_sub_days(Days::new(7))
                     .unwrap_or(self.active_date),
                     Depth::Month => self.active_date.checked_sub_months(Months::new(1))
@@ -141,13 +504,21 @@ impl CalendarWidget{
                 self.edit_dialog = Some(DialogOption::Add(date));
                 self.dialog_appointment = DialogAppointment::default();
                 self.dialog_appointment.date = date::naive_date_time_as_string(date);
+                if date.time() != NaiveTime::MIN {
+                    // Added from a time slot, prefill a one hour appointment
+                    self.dialog_appointment.all_day = false;
+                    self.dialog_appointment.start = date.format("%H:%M").to_string();
+                    self.dialog_appointment.end = "+1h".to_string();
+                }
                 Command::none()
             }
-            Message::EditAppointment(id) => {
+            Message::EditAppointment(key, original) => {
                 for app in &self.appointments {
-                    if id == app.id {
-                        let appointment = app.clone();
-                        self.edit_dialog = Some(DialogOption::Edit(appointment.clone()));
+                    if key == app.key() {
+                        let occurrence = app.occurrence(original);
+                        // Overridden occurrences have no rule of their own, show the one of the series
+                        let appointment = Appointment { recurrence: app.recurrence.clone(), ..occurrence.appointment.clone() };
+                        self.edit_dialog = Some(DialogOption::Edit(key, occurrence.original));
                         self.dialog_appointment = DialogAppointment::from_appointment(appointment);
                         break;
                     }
@@ -158,18 +529,37 @@ impl CalendarWidget{
                 self.dialog_appointment.date = string.clone();
                 Command::none()
             }
+            Message::DialogAllDay(all_day) => {
+                self.dialog_appointment.all_day = all_day;
+                Command::none()
+            }
+            Message::DialogStart(string) => {
+                self.dialog_appointment.start = string;
+                Command::none()
+            }
+            Message::DialogEnd(string) => {
+                self.dialog_appointment.end = string;
+                Command::none()
+            }
             Message::DialogPriority(priority) => {
                 self.dialog_appointment.priority = priority;
                 widget::focus_next()
             }
-            Message::DialogWarning(string) => {
-                self.dialog_appointment.warning = string.clone();
-                let date = valid_date(string).ok();
-                if date != None {
-                    widget::focus_next()
-                } else {
-                    Command::none()
+            Message::DialogAlert(index, string) => {
+                if let Some(alert) = self.dialog_appointment.alerts.get_mut(index) {
+                    *alert = string;
+                }
+                Command::none()
+            }
+            Message::DialogAddAlert => {
+                self.dialog_appointment.alerts.push("-PT15M".to_string());
+                Command::none()
+            }
+            Message::DialogRemoveAlert(index) => {
+                if index < self.dialog_appointment.alerts.len() {
+                    self.dialog_appointment.alerts.remove(index);
                 }
+                Command::none()
             }
             Message::DialogTags(string) => {
                 self.dialog_appointment.tags = string.clone();
@@ -180,163 +570,1520 @@ impl CalendarWidget{
                 self.dialog_appointment.description = string.clone();
                 Command::none()
             }
+            Message::DialogRepeat(repeat) => {
+                self.dialog_appointment.repeat = repeat;
+                Command::none()
+            }
+            Message::DialogFrequency(frequency) => {
+                self.dialog_appointment.frequency = frequency;
+                Command::none()
+            }
+            Message::DialogInterval(string) => {
+                self.dialog_appointment.interval = string;
+                Command::none()
+            }
+            Message::DialogByDay(string) => {
+                self.dialog_appointment.by_day = string;
+                Command::none()
+            }
+            Message::DialogCount(string) => {
+                self.dialog_appointment.count = string;
+                Command::none()
+            }
+            Message::DialogUntil(string) => {
+                self.dialog_appointment.until = string;
+                Command::none()
+            }
             Message::DialogCancel => {
                 self.edit_dialog = None;
+                self.quick_add = None;
+                self.pending_edit = None;
+                Command::none()
+            }
+            Message::DialogSubmit => {
+                let calendar = self.dialog_appointment.calendar.clone();
+                let edited = match &self.edit_dialog {
+                    Some(DialogOption::Edit(key, _original)) => Some(key.clone()),
+                    _ => None,
+                };
+                // Moving an appointment to another calendar may need another id there
+                let id = match &edited {
+                    Some(key) if key.calendar == calendar => key.id,
+                    _ => self.new_id_in(&calendar),
+                };
+                let Ok(mut new) = self.dialog_appointment.to_appointment(id) else {
+                    return Command::none()
+                };
+                let editing = edited.is_some();
+                if edited.as_ref().is_some_and(|key| self.is_read_only(key)) {
+                    return Command::none()
+                }
+                let before = self.appointments.clone();
+                if let Some(index) = self.appointments.iter().position(|x| Some(x.key()) == edited) {
+                    new.uid = self.appointments[index].uid.clone();
+                    if self.appointments[index].recurrence.is_some() {
+                        self.pending_edit = Some(new);
+                        return Command::none()
+                    }
+                    self.appointments.remove(index);
+                }
+                let label = match editing {
+                    true => t!("history-edit", description = new.description()),
+                    false => t!("history-add", description = new.description()),
+                };
+                self.appointments.push(new);
+                self.commit(label, before);
+                self.edit_dialog = None;
+                Command::none()
+            }
+            Message::ContextMenu(key, original) => {
+                self.edit_dialog = Some(DialogOption::ContextMenu(key, original));
+                Command::none()
+            }
+            Message::DeleteAppointment(key, original) => {
+                if self.is_read_only(&key) {
+                    return Command::none()
+                }
+                self.edit_dialog = Some(DialogOption::ConfirmDelete(key, original));
+                Command::none()
+            }
+            Message::ConfirmDelete(scope) => {
+                let Some(DialogOption::ConfirmDelete(key, original)) = self.edit_dialog.take() else {
+                    return Command::none()
+                };
+                let Some(index) = self.appointments.iter().position(|x| x.key() == key) else {
+                    return Command::none()
+                };
+                let before = self.appointments.clone();
+                let description = self.appointments[index].occurrence(original).appointment.description;
+                let series = &mut self.appointments[index];
+                let remove = match scope {
+                    _ if series.recurrence.is_none() => true,
+                    EditScope::All => true,
+                    EditScope::This => {
+                        series.exclude(original);
+                        false
+                    }
+                    EditScope::Following => series.truncate_before(original) == 0,
+                };
+                if remove {
+                    self.appointments.remove(index);
+                }
+                self.status = t!("status-deleted", description = description);
+                self.commit(t!("history-delete", description = description), before);
+                Command::none()
+            }
+            Message::DialogTagSuggestion(tag) => {
+                let mut tags: Vec<&str> = self.dialog_appointment.tags.split(',').map(str::trim).collect();
+                tags.pop();
+                tags.push(&tag);
+                self.dialog_appointment.tags = format!("{}, ", tags.join(", "));
+                Command::none()
+            }
+            Message::ToggleTags => {
+                self.show_tags = !self.show_tags;
+                self.tag_rename = None;
+                Command::none()
+            }
+            Message::TagVisible(tag, visible) => {
+                self.hidden_tags.retain(|hidden| *hidden != tag);
+                if !visible {
+                    self.hidden_tags.push(tag);
+                }
+                Command::none()
+            }
+            Message::RenameTag(tag) => {
+                self.tag_rename = Some((tag.clone(), tag));
+                Command::none()
+            }
+            Message::TagNewName(name) => {
+                if let Some((_, new_name)) = &mut self.tag_rename {
+                    *new_name = name;
+                }
+                Command::none()
+            }
+            Message::ConfirmRenameTag => {
+                let Some((from, to)) = self.tag_rename.take() else {
+                    return Command::none()
+                };
+                let to = to.trim().to_string();
+                if to.is_empty() || to.contains(',') || to == from {
+                    return Command::none()
+                }
+                let before = self.appointments.clone();
+                let merged = self.appointments.iter().any(|x| x.tag_list().any(|tag| *tag == to));
+                let read_only: Vec<AppointmentKey> = self.appointments.iter().map(Appointment::key).filter(|key| self.is_read_only(key)).collect();
+                for appointment in self.appointments.iter_mut().filter(|x| !read_only.contains(&x.key())) {
+                    appointment.rename_tag(&from, &to);
+                }
+                if self.hidden_tags.contains(&from) {
+                    self.hidden_tags.retain(|hidden| *hidden != from && *hidden != to);
+                    self.hidden_tags.push(to.clone());
+                }
+                let label = if merged { t!("history-merge-tag", from = from, to = to) } else { t!("history-rename-tag", from = from, to = to) };
+                self.commit(label, before);
+                Command::none()
+            }
+            Message::CancelRenameTag => {
+                self.tag_rename = None;
+                Command::none()
+            }
+            Message::QuickAdd => {
+                self.edit_dialog = None;
+                self.quick_add = Some("".to_string());
+                text_input::focus(text_input::Id::new(QUICK_ADD))
+            }
+            Message::QuickAddInput(string) => {
+                self.quick_add = Some(string);
+                Command::none()
+            }
+            Message::QuickAddSubmit | Message::QuickAddDialog => {
+                let Some(input) = self.quick_add.take() else {
+                    return Command::none()
+                };
+                let parsed = quick_add::parse(&input, chrono::Local::now().naive_local());
+                if parsed.problems.is_empty() && matches!(message, Message::QuickAddSubmit) {
+                    let before = self.appointments.clone();
+                    let appointment = Appointment { id: self.new_id_in(&None), ..parsed.appointment };
+                    let label = t!("history-add", description = appointment.description());
+                    self.active_date = appointment.date.date().and_time(NaiveTime::MIN);
+                    self.appointments.push(appointment);
+                    self.commit(label, before);
+                } else {
+                    // Let the user complete what the quick-add bar could not read
+                    let date = parsed.appointment.date.date().and_time(NaiveTime::MIN);
+                    self.edit_dialog = Some(DialogOption::Add(date));
+                    self.dialog_appointment = DialogAppointment::from_appointment(parsed.appointment);
+                }
+                Command::none()
+            }
+            Message::CheckReminders => {
+                self.check_reminders();
+                Command::none()
+            }
+            Message::SnoozeReminder(ref key, occurrence, due) | Message::DismissReminder(ref key, occurrence, due) => {
+                // The list may have been checked again since the banner was shown
+                let Some(due) = self.reminders.iter().find(|x| x.key == *key && x.occurrence == occurrence && x.due == due) else {
+                    return Command::none()
+                };
+                let acknowledged = match message {
+                    Message::SnoozeReminder(..) => due.snooze(chrono::Local::now().naive_local()),
+                    _ => due.dismiss(),
+                };
+                reminder::acknowledge(&mut self.acknowledged, acknowledged);
+                self.check_reminders();
+                self.save();
+                Command::none()
+            }
+            Message::JumpTo(date) => {
+                self.active_date = date;
+                self.depth = if self.modifiers.control() { Depth::Week } else { Depth::Month };
+                Command::none()
+            }
+            Message::Undo => {
+                let before = (self.appointments.clone(), self.tasks.clone());
+                if let Some(label) = self.history.undo(&mut self.appointments, &mut self.tasks) {
+                    self.status = t!("status-undid", label = label);
+                    if before != (self.appointments.clone(), self.tasks.clone()) {
+                        self.save();
+                    }
+                }
+                Command::none()
+            }
+            Message::Redo => {
+                let before = (self.appointments.clone(), self.tasks.clone());
+                if let Some(label) = self.history.redo(&mut self.appointments, &mut self.tasks) {
+                    self.status = t!("status-redid", label = label);
+                    if before != (self.appointments.clone(), self.tasks.clone()) {
+                        self.save();
+                    }
+                }
+                Command::none()
+            }
+            Message::DuplicateAppointment(key, original) => {
+                let Some(appointment) = self.appointments.iter().find(|x| x.key() == key) else {
+                    return Command::none()
+                };
+                let mut duplicate = appointment.duplicate(original);
+                if self.is_read_only(&key) {
+                    // The duplicate can be changed, so it goes into the main calendar
+                    duplicate.calendar = None;
+                }
+                duplicate.id = self.new_id_in(&duplicate.calendar);
+                let (key, original) = (duplicate.key(), duplicate.date);
+                let (before, label) = (self.appointments.clone(), t!("history-duplicate", description = duplicate.description()));
+                self.appointments.push(duplicate);
+                self.commit(label, before);
+                self.update(Message::EditAppointment(key, original))
+            }
+            Message::MoveAppointment(key, original) => {
+                if self.is_read_only(&key) {
+                    return Command::none()
+                }
+                self.move_date = original.fmt();
+                self.edit_dialog = Some(DialogOption::Move(key, original));
+                Command::none()
+            }
+            Message::MoveDate(string) => {
+                self.move_date = string;
+                Command::none()
+            }
+            Message::ConfirmMove => {
+                let Some(DialogOption::Move(key, original)) = self.edit_dialog.clone() else {
+                    return Command::none()
+                };
+                let Ok(target) = valid_date(self.move_date.clone()) else {
+                    return Command::none()
+                };
+                let before = self.appointments.clone();
+                let mut label = String::new();
+                if let Some(appointment) = self.appointments.iter_mut().find(|x| x.key() == key) {
+                    label = t!("history-move", description = appointment.description(), date = target.fmt());
+                    if appointment.recurrence.is_some() {
+                        let moved = appointment.occurrence(original).appointment.moved_to(target.date());
+                        appointment.override_occurrence(original, moved);
+                    } else {
+                        *appointment = appointment.moved_to(target.date());
+                    }
+                }
+                self.edit_dialog = None;
+                self.commit(label, before);
+                Command::none()
+            }
+            Message::DragAppointment(key, original) => {
+                self.drag = self.appointments.iter()
+                    .find(|x| x.key() == key)
+                    .map(|appointment| Drag { occurrence: appointment.occurrence(original), over: None });
+                Command::none()
+            }
+            Message::DragOver(slot) => {
+                if let Some(drag) = &mut self.drag {
+                    drag.over = Some(slot);
+                }
+                Command::none()
+            }
+            Message::DropAppointment(slot) => {
+                let Some(Drag { occurrence: Occurrence { appointment: dragged, original }, .. }) = self.drag.take() else {
+                    return Command::none()
+                };
+                let moved = match slot {
+                    DropSlot::Day(day) => dragged.moved_to(day),
+                    DropSlot::Hour(day, hour) => dragged.moved_to_time(day, NaiveTime::from_hms_opt(hour, 0, 0).unwrap()),
+                };
+                let copy = self.modifiers.control();
+                let read_only = self.is_read_only(&dragged.key());
+                if !copy && (moved == dragged || read_only) {
+                    return Command::none()
+                }
+                let series = self.appointments.iter().find(|x| x.key() == dragged.key() && x.recurrence.is_some());
+                if let (false, Some(series)) = (copy, series) {
+                    // Like an edit in the dialog, moving an occurrence asks which occurrences to move
+                    self.pending_edit = Some(Appointment { recurrence: series.recurrence.clone(), exceptions: vec![], overrides: vec![], ..moved });
+                    self.edit_dialog = Some(DialogOption::Edit(dragged.key(), original));
+                    return Command::none()
+                }
+                let before = self.appointments.clone();
+                let label = if copy {
+                    // Copying an occurrence of a series creates a single appointment, like duplicating it
+                    let calendar = if read_only { None } else { moved.calendar.clone() };
+                    let copied = Appointment { id: self.new_id_in(&calendar), recurrence: None, exceptions: vec![], overrides: vec![], calendar, uid: None, ..moved };
+                    let label = t!("history-copy", description = copied.description(), date = copied.date.fmt());
+                    self.appointments.push(copied);
+                    label
+                } else {
+                    let Some(appointment) = self.appointments.iter_mut().find(|x| x.key() == dragged.key()) else {
+                        return Command::none()
+                    };
+                    let label = t!("history-move", description = moved.description(), date = moved.date.fmt());
+                    *appointment = moved;
+                    label
+                };
+                self.commit(label, before);
+                Command::none()
+            }
+            Message::AddTask => {
+                self.dialog_task = DialogTask::from_task(&Task::default());
+                self.edit_dialog = Some(DialogOption::Task(None));
+                Command::none()
+            }
+            Message::EditTask(id) => {
+                if let Some(task) = self.tasks.iter().find(|x| x.id == id) {
+                    self.dialog_task = DialogTask::from_task(task);
+                    self.edit_dialog = Some(DialogOption::Task(Some(id)));
+                }
+                Command::none()
+            }
+            Message::DeleteTask(id) => {
+                let before = self.tasks.clone();
+                let Some(index) = self.tasks.iter().position(|x| x.id == id) else {
+                    return Command::none()
+                };
+                let task = self.tasks.remove(index);
+                self.edit_dialog = None;
+                self.commit_tasks(t!("history-delete-task", description = task.description), before);
+                Command::none()
+            }
+            Message::ToggleTask(id, done) => {
+                let before = self.tasks.clone();
+                let Some(task) = self.tasks.iter_mut().find(|x| x.id == id) else {
+                    return Command::none()
+                };
+                task.set_done(done, PDate::now());
+                let label = match done {
+                    true => t!("history-done-task", description = task.description),
+                    false => t!("history-reopen-task", description = task.description),
+                };
+                self.commit_tasks(label, before);
+                Command::none()
+            }
+            Message::ToggleChecklistItem(id, index, done) => {
+                let before = self.tasks.clone();
+                let Some(task) = self.tasks.iter_mut().find(|x| x.id == id) else {
+                    return Command::none()
+                };
+                let Some(item) = task.checklist.get_mut(index) else {
+                    return Command::none()
+                };
+                item.done = done;
+                let label = t!("history-checklist", description = task.description);
+                self.commit_tasks(label, before);
+                Command::none()
+            }
+            Message::TaskDescription(string) => {
+                self.dialog_task.description = string;
+                Command::none()
+            }
+            Message::TaskDue(string) => {
+                self.dialog_task.due = string;
+                Command::none()
+            }
+            Message::TaskPriority(priority) => {
+                self.dialog_task.priority = priority;
+                Command::none()
+            }
+            Message::TaskItem(index, string) => {
+                if let Some(item) = self.dialog_task.checklist.get_mut(index) {
+                    item.text = string;
+                }
+                Command::none()
+            }
+            Message::TaskItemDone(index, done) => {
+                if let Some(item) = self.dialog_task.checklist.get_mut(index) {
+                    item.done = done;
+                }
+                Command::none()
+            }
+            Message::TaskAddItem => {
+                self.dialog_task.checklist.push(ChecklistItem::default());
+                Command::none()
+            }
+            Message::TaskRemoveItem(index) => {
+                if index < self.dialog_task.checklist.len() {
+                    self.dialog_task.checklist.remove(index);
+                }
+                Command::none()
+            }
+            Message::TaskSubmit => {
+                let Some(DialogOption::Task(id)) = self.edit_dialog else {
+                    return Command::none()
+                };
+                let existing = id.and_then(|id| self.tasks.iter().find(|x| x.id == id).cloned());
+                let editing = existing.is_some();
+                let base = existing.unwrap_or(Task { id: new_id(&self.tasks), ..Task::default() });
+                let Ok(new) = self.dialog_task.to_task(base) else {
+                    return Command::none()
+                };
+                let before = self.tasks.clone();
+                let label = match editing {
+                    true => t!("history-edit-task", description = new.description),
+                    false => t!("history-add-task", description = new.description),
+                };
+                self.tasks.retain(|x| x.id != new.id);
+                self.tasks.push(new);
+                self.edit_dialog = None;
+                self.commit_tasks(label, before);
+                Command::none()
+            }
+            Message::DialogCalendar(calendar) => {
+                self.dialog_appointment.calendar = (!calendar.is_main()).then_some(calendar.file);
+                Command::none()
+            }
+            Message::ToggleCalendars => {
+                self.show_calendars = !self.show_calendars;
+                Command::none()
+            }
+            Message::CalendarVisible(file, visible) => {
+                if let Some(calendar) = self.calendars.iter_mut().find(|x| x.file == file) {
+                    calendar.visible = visible;
+                }
+                self.save_calendars();
+                Command::none()
+            }
+            Message::CalendarReadOnly(file, read_only) => {
+                if let Some(calendar) = self.calendars.iter_mut().find(|x| x.file == file && !x.is_main()) {
+                    calendar.read_only = read_only;
+                }
+                self.save_calendars();
+                Command::none()
+            }
+            Message::NewCalendarName(name) => {
+                self.new_calendar = name;
+                Command::none()
+            }
+            Message::AddCalendar => {
+                if self.new_calendar.trim().is_empty() {
+                    return Command::none()
+                }
+                let calendar = calendars::new_calendar(&self.new_calendar, &self.calendars);
+                self.calendars.push(calendar);
+                self.new_calendar.clear();
+                self.save_calendars();
                 Command::none()
             }
-            Message::DialogSubmit(appointment) => {
-                if valid_date(self.dialog_appointment.date.clone()).is_ok() &&
-                    valid_date(self.dialog_appointment.warning.clone()).is_ok() && 
-                    valid_tags(self.dialog_appointment.tags.clone()).is_ok() {
-                        if appointment != None {
-                            let index = self.appointments.iter().position(|x| *x == appointment.clone().unwrap()).unwrap();
-                            self.appointments.remove(index);
+            Message::RestoreBackup(backup) => {
+                match storage::restore(&backup, &storage::data_file()) {
+                    Ok(data) => {
+                        // The backup only holds the main calendar
+                        let others: Vec<Appointment> = self.appointments.drain(..).filter(|x| x.calendar.is_some()).collect();
+                        self.appointments = data.appointments;
+                        self.appointments.extend(others);
+                        self.tasks = data.tasks;
+                        self.acknowledged = data.reminders;
+                        self.load_error = None;
+                        // Undoing would bring back changes the backup does not know about
+                        self.history = History::default();
+                        self.edit_dialog = None;
+                        self.status = t!("status-restored", path = backup.display());
+                    }
+                    Err(error) => self.load_error = Some(t!("error-restore", path = backup.display(), error = error)),
+                }
+                Command::none()
+            }
+            Message::DiscardSaved => {
+                // The damaged file is kept as the newest backup by the next save
+                self.load_error = None;
+                Command::none()
+            }
+            Message::IcsPath(string) => {
+                self.ics_path = string;
+                Command::none()
+            }
+            Message::ImportIcs => {
+                self.status = match ical::import_file(self.ics_path.as_ref()) {
+                    Ok(imported) => {
+                        let (count, tasks) = (imported.appointments.len(), imported.tasks.len());
+                        let before = (self.appointments.clone(), self.tasks.clone());
+                        let calendars = &self.calendars;
+                        let skipped = ical::merge(imported, &mut self.appointments, &mut self.tasks, |appointment| {
+                            calendars::calendar_of(calendars, appointment).is_none_or(Calendar::is_writable)
+                        });
+                        let label = t!("history-import", count = count, tasks = tasks);
+                        if let Some(change) = Change::between_all(label, (&before.0, &self.appointments), (&before.1, &self.tasks)) {
+                            self.history.push(change);
+                        }
+                        self.check_reminders();
+                        self.save();
+                        if skipped > 0 {
+                            t!("status-imported-skipped", count = count, tasks = tasks, skipped = skipped)
+                        } else {
+                            t!("status-imported", count = count, tasks = tasks)
                         }
-                        self.appointments.push(
-                            Appointment {
-                                id: new_id(self.appointments.clone()),
-                                date: valid_date(self.dialog_appointment.date.clone()).unwrap(),
-                                priority: self.dialog_appointment.priority,
-                                warning: valid_date(self.dialog_appointment.warning.clone()).unwrap(),
-                                tags: Some(valid_tags(self.dialog_appointment.tags.clone()).unwrap()),
-                                description: self.dialog_appointment.description.clone()
+                    }
+                    Err(error) => t!("status-import-failed", error = error),
+                };
+                Command::none()
+            }
+            Message::ExportIcs => {
+                self.status = match ical::export_file(self.ics_path.as_ref(), &self.appointments, &self.tasks) {
+                    Ok(()) => t!("status-exported", count = self.appointments.len(), tasks = self.tasks.len()),
+                    Err(error) => t!("status-export-failed", error = error),
+                };
+                Command::none()
+            }
+            Message::DialogScope(scope) => {
+                let (Some(DialogOption::Edit(key, original)), Some(edited)) = (self.edit_dialog.take(), self.pending_edit.take()) else {
+                    return Command::none()
+                };
+                // The dialog already picked a free id if the series goes to another calendar
+                let new_id = self.new_id_in(&edited.calendar);
+                let moved_id = edited.id;
+                let before = self.appointments.clone();
+                let label = t!("history-edit", description = edited.description());
+                let Some(series) = self.appointments.iter_mut().find(|x| x.key() == key) else {
+                    return Command::none()
+                };
+                match scope {
+                    // A single occurrence can not be in another calendar than its series
+                    EditScope::This if edited.calendar != series.calendar => return Command::none(),
+                    EditScope::This => series.override_occurrence(original, edited),
+                    EditScope::All => {
+                        series.id = moved_id;
+                        series.edit_series(original, edited);
+                    }
+                    EditScope::Following => {
+                        let kept = series.clone().truncate_before(original);
+                        if kept == 0 {
+                            series.id = moved_id;
+                            series.edit_series(original, edited);
+                        } else {
+                            series.truncate_before(original);
+                            let mut following = Appointment { id: new_id, uid: None, ..edited };
+                            if let Some(recurrence) = &mut following.recurrence {
+                                recurrence.count = recurrence.count.map(|count| count.saturating_sub(kept));
                             }
-                        );
-                        save_appointments(self.appointments.clone());
-                        self.edit_dialog = None;
-                    }   
+                            self.appointments.push(following);
+                        }
+                    }
+                }
+                self.commit(label, before);
                 Command::none()
             }
-            _ => Command::none()
         }
     }
-    
-    pub fn view<'a>(&self) -> Element<'a, Message> {
-        let content = match self.depth {
-            Depth::Year => {
-                self.view_year(self.active_date)
-            },
-            Depth::Month => {
-                self.view_month(self.active_date)
-            }
-            Depth::Week => {
-                self.view_week(self.active_date)
-            }
+    
+    pub fn view<'a>(&self) -> Element<'a, Message> {
+        let content = match self.depth {
+            Depth::Year => {
+                self.view_year(self.active_date)
+            },
+            Depth::Month => {
+                self.view_month(self.active_date)
+            }
+            Depth::Week => {
+                self.view_week(self.active_date)
+            }
+            Depth::Day => {
+                self.view_day(self.active_date)
+            }
+        };
+        let content = if self.show_tags {
+            row![self.view_tags(), content].spacing(10).into()
+        } else {
+            content
+        };
+        let content = if self.show_calendars {
+            row![self.view_calendars(), content].spacing(10).into()
+        } else {
+            content
+        };
+        let content: Element<'a, Message> = column![self.view_toolbar(), content]
+            .spacing(5)
+            .into();
+        self.with_dialog(content, |message| message)
+    }
+
+    /// Shows the open dialog of the calendar over `content`
+    ///
+    /// Other screens use this to edit appointments with the dialogs of the calendar,
+    /// `map` turns the messages of the dialog into messages of the screen.
+    pub fn with_dialog<'a, M: Clone + 'a>(&self, content: Element<'a, M>, map: fn(Message) -> M) -> Element<'a, M> {
+        if let Some(error) = &self.load_error {
+            return Modal::new(content, self.view_load_error(error).map(map)).into()
+        }
+        if self.quick_add.is_some() {
+            return Modal::new(content, self.view_quick_add().map(map))
+                .on_blur(map(Message::DialogCancel))
+                .into()
+        }
+        if self.edit_dialog.is_none() {
+            return content
+        }
+        let modal = match &self.edit_dialog {
+            Some(DialogOption::ContextMenu(key, original)) => self.view_context_menu(key, *original),
+            Some(DialogOption::ConfirmDelete(key, original)) => self.view_delete_dialog(key, *original),
+            Some(DialogOption::Move(..)) => self.view_move_dialog(),
+            Some(DialogOption::Task(id)) => self.view_task_dialog(*id),
+            _ if self.pending_edit.is_some() => self.view_scope_dialog(),
+            _ => self.view_dialog(),
+        };
+        Modal::new(content, modal.map(map))
+            .on_blur(map(Message::DialogCancel))
+            .into()
+    }
+
+    /// The appointments of the visible calendars
+    pub fn appointments(&self) -> Vec<Appointment> {
+        self.appointments.iter()
+            .filter(|x| self.calendar_of(x).is_none_or(|calendar| calendar.visible))
+            .cloned()
+            .collect()
+    }
+
+    pub fn tasks(&self) -> &[Task] {
+        &self.tasks
+    }
+
+    fn view_context_menu<'a>(&self, key: &AppointmentKey, original: PDate) -> Element<'a, Message> {
+        let description = self.appointments.iter()
+            .find(|x| x.key() == *key)
+            .map(|x| x.occurrence(original).appointment.description)
+            .unwrap_or_default();
+        let mut menu = column![
+            text(description),
+            button(text(t!("button-edit")))
+                .width(Length::Fill)
+                .on_press(Message::EditAppointment(key.clone(), original)),
+            button(text(t!("button-duplicate")))
+                .width(Length::Fill)
+                .on_press(Message::DuplicateAppointment(key.clone(), original)),
+        ]
+        .spacing(10);
+        if !self.is_read_only(key) {
+            menu = menu
+                .push(
+                    button(text(t!("button-move-to-date")))
+                        .width(Length::Fill)
+                        .on_press(Message::MoveAppointment(key.clone(), original))
+                )
+                .push(
+                    button(text(t!("button-delete")))
+                        .width(Length::Fill)
+                        .style(theme::Button::Destructive)
+                        .on_press(Message::DeleteAppointment(key.clone(), original))
+                );
+        }
+        container(menu)
+        .width(200)
+        .padding(10)
+        .style(theme::Container::Custom(Box::new(Dialog)))
+        .into()
+    }
+
+    fn view_delete_dialog<'a>(&self, key: &AppointmentKey, original: PDate) -> Element<'a, Message> {
+        let Some(appointment) = self.appointments.iter().find(|x| x.key() == *key) else {
+            return text("").into()
+        };
+        let mut content = column![
+            text(t!("delete-question", description = appointment.occurrence(original).appointment.description)),
+        ]
+        .spacing(10);
+        if appointment.recurrence.is_some() {
+            content = content.push(
+                column![
+                    button(text(t!("delete-this")))
+                        .width(Length::Fill)
+                        .style(theme::Button::Destructive)
+                        .on_press(Message::ConfirmDelete(EditScope::This)),
+                    button(text(t!("delete-following")))
+                        .width(Length::Fill)
+                        .style(theme::Button::Destructive)
+                        .on_press(Message::ConfirmDelete(EditScope::Following)),
+                    button(text(t!("delete-all")))
+                        .width(Length::Fill)
+                        .style(theme::Button::Destructive)
+                        .on_press(Message::ConfirmDelete(EditScope::All)),
+                    button(text(t!("button-cancel")))
+                        .on_press(Message::DialogCancel),
+                ]
+                .spacing(10)
+            );
+        } else {
+            content = content.push(
+                row![
+                    button(text(t!("button-cancel")))
+                        .on_press(Message::DialogCancel),
+                    Space::new(Length::Fill, Length::Shrink),
+                    button(text(t!("button-delete")))
+                        .style(theme::Button::Destructive)
+                        .on_press(Message::ConfirmDelete(EditScope::All)),
+                ]
+            );
+        }
+        container(content)
+            .width(300)
+            .padding(10)
+            .style(theme::Container::Custom(Box::new(Dialog)))
+            .into()
+    }
+
+    fn view_move_dialog<'a>(&self) -> Element<'a, Message> {
+        container(
+            column![
+                column![
+                    text(t!("move-to")).size(12),
+                    text_input(date_format::display_format().placeholder(), self.move_date.as_str())
+                        .on_input(Message::MoveDate)
+                        .on_submit(Message::ConfirmMove)
+                ],
+                row![
+                    button(text(t!("button-cancel")))
+                        .on_press(Message::DialogCancel),
+                    Space::new(Length::Fill, Length::Shrink),
+                    button(text(t!("button-move")))
+                        .on_press(Message::ConfirmMove),
+                ]
+            ]
+            .spacing(20),
+        )
+        .width(300)
+        .padding(10)
+        .style(theme::Container::Custom(Box::new(Dialog)))
+        .into()
+    }
+
+    /// The quick-add bar with a preview of the appointment it would add
+    fn view_quick_add<'a>(&self) -> Element<'a, Message> {
+        let input = self.quick_add.clone().unwrap_or_default();
+        let parsed = quick_add::parse(&input, chrono::Local::now().naive_local());
+        let appointment = &parsed.appointment;
+        let mut preview = column![].spacing(5);
+        if !input.trim().is_empty() {
+            preview = preview.push(text(appointment.description()).size(20));
+            preview = preview.push(text(format!("{}, {}", date_format::format_long(appointment.date.date()), appointment.time_string())));
+            if let Some(recurrence) = &appointment.recurrence {
+                let days = recurrence.by_day.iter().map(ByDay::to_string).collect::<Vec<String>>().join(", ");
+                preview = preview.push(text(t!("quick-add-repeats", frequency = recurrence.frequency, interval = recurrence.interval, days = days)).size(12));
+            }
+            preview = preview.push(text(t!("label-priority", priority = appointment.priority)).size(12));
+            let tags: Vec<String> = appointment.tag_list().cloned().collect();
+            if !tags.is_empty() {
+                preview = preview.push(text(t!("label-tags", tags = tags.join(", "))).size(12));
+            }
+            for alert in &appointment.alerts {
+                preview = preview.push(text(t!("label-reminder", alert = alert)).size(12));
+            }
+            for problem in &parsed.problems {
+                preview = preview.push(text(t!("quick-add-unclear", problem = problem)).size(12));
+            }
+        }
+        let submit = if parsed.problems.is_empty() { t!("button-add") } else { t!("quick-add-complete") };
+        container(
+            column![
+                text_input(&t!("quick-add-placeholder"), &input)
+                    .id(text_input::Id::new(QUICK_ADD))
+                    .on_input(Message::QuickAddInput)
+                    .on_submit(Message::QuickAddSubmit),
+                preview,
+                row![
+                    button(text(t!("button-cancel")))
+                        .on_press(Message::DialogCancel),
+                    Space::new(Length::Fill, Length::Shrink),
+                    button(text(t!("quick-add-open-dialog")))
+                        .style(theme::Button::Secondary)
+                        .on_press(Message::QuickAddDialog),
+                    button(text(submit))
+                        .on_press(Message::QuickAddSubmit),
+                ]
+                .spacing(10),
+            ]
+            .spacing(20),
+        )
+        .width(500)
+        .padding(10)
+        .style(theme::Container::Custom(Box::new(Dialog)))
+        .into()
+    }
+
+    fn view_load_error<'a>(&self, error: &str) -> Element<'a, Message> {
+        let mut content = column![
+            text(t!("load-error-title")).size(20),
+            text(error),
+        ]
+        .spacing(10);
+        for backup in storage::backups(&storage::data_file()) {
+            let modified = backup.metadata()
+                .and_then(|metadata| metadata.modified())
+                .map(|time| chrono::DateTime::<chrono::Local>::from(time).naive_local())
+                .map(|time| format!(" ({} {})", date_format::format_date(time.date()), time.format("%H:%M")))
+                .unwrap_or_default();
+            content = content.push(
+                button(text(t!("load-error-restore", path = backup.display(), modified = modified)))
+                    .width(Length::Fill)
+                    .on_press(Message::RestoreBackup(backup))
+            );
+        }
+        content = content.push(
+            button(text(t!("load-error-discard")))
+                .width(Length::Fill)
+                .on_press(Message::DiscardSaved)
+        );
+        container(content)
+            .width(500)
+            .padding(10)
+            .style(theme::Container::Custom(Box::new(Dialog)))
+            .into()
+    }
+
+    fn view_toolbar<'a>(&self) -> Element<'a, Message> {
+        let mut toolbar = row![
+            text_input(&t!("toolbar-ics-placeholder"), self.ics_path.as_str())
+                .on_input(Message::IcsPath)
+                .width(300),
+            button(text(t!("toolbar-import")))
+                .on_press(Message::ImportIcs),
+            button(text(t!("toolbar-export")))
+                .on_press(Message::ExportIcs),
+            button(text(t!("toolbar-tags")))
+                .on_press(Message::ToggleTags),
+            button(text(t!("toolbar-calendars")))
+                .on_press(Message::ToggleCalendars),
+            text(self.status.as_str()),
+        ]
+        .spacing(10)
+        .align_items(iced::Alignment::Center);
+        if self.history.can_undo() {
+            toolbar = toolbar.push(button(text(t!("toolbar-undo"))).on_press(Message::Undo));
+        }
+        if self.history.can_redo() {
+            toolbar = toolbar.push(button(text(t!("toolbar-redo"))).on_press(Message::Redo));
+        }
+        toolbar.into()
+    }
+
+    /// Banner listing the due reminders, empty if there are none
+    pub fn view_reminders<'a>(&self) -> Element<'a, Message> {
+        let mut banner = column![].spacing(5);
+        for due in &self.reminders {
+            let appointment = &due.appointment;
+            banner = banner.push(
+                row![
+                    text(t!("reminder-banner", description = appointment.description(), date = appointment.date.fmt(), time = appointment.time_string()))
+                        .width(Length::Fill),
+                    button(text(t!("button-open"))).on_press(Message::EditAppointment(due.key.clone(), due.occurrence)),
+                    button(text(t!("reminder-snooze", minutes = reminder::SNOOZE_MINUTES)))
+                        .style(theme::Button::Secondary)
+                        .on_press(Message::SnoozeReminder(due.key.clone(), due.occurrence, due.due)),
+                    button(text(t!("button-dismiss")))
+                        .style(theme::Button::Secondary)
+                        .on_press(Message::DismissReminder(due.key.clone(), due.occurrence, due.due)),
+                ]
+                .spacing(10)
+                .align_items(iced::Alignment::Center)
+            );
+        }
+        if self.reminders.is_empty() {
+            return banner.into()
+        }
+        container(banner)
+            .width(Length::Fill)
+            .padding(10)
+            .style(theme::Container::Box)
+            .into()
+    }
+
+    /// Sidebar listing all tags, hiding their appointments and renaming them
+    fn view_tags<'a>(&self) -> Element<'a, Message> {
+        let mut list = column![text(t!("tags-title")).size(20)].spacing(5).width(200);
+        let counts = tag_counts(&self.appointments);
+        if counts.is_empty() {
+            list = list.push(text(t!("tags-empty")).size(12));
+        }
+        for (tag, count) in counts {
+            if let Some((from, to)) = &self.tag_rename {
+                if *from == tag {
+                    list = list.push(
+                        column![
+                            text_input(&t!("tags-new-name"), to)
+                                .on_input(Message::TagNewName)
+                                .on_submit(Message::ConfirmRenameTag),
+                            row![
+                                button(text(t!("button-rename"))).on_press(Message::ConfirmRenameTag),
+                                button(text(t!("button-cancel"))).style(theme::Button::Secondary).on_press(Message::CancelRenameTag),
+                            ]
+                            .spacing(5),
+                            text(t!("tags-merge-hint")).size(12),
+                        ]
+                        .spacing(5)
+                    );
+                    continue
+                }
+            }
+            let visible = !self.hidden_tags.contains(&tag);
+            let toggle = tag.clone();
+            list = list.push(
+                row![
+                    checkbox(format!("{} ({})", tag, count), visible, move |visible| Message::TagVisible(toggle.clone(), visible))
+                        .width(Length::Fill),
+                    button(text(t!("button-rename")).size(12))
+                        .style(theme::Button::Text)
+                        .on_press(Message::RenameTag(tag)),
+                ]
+                .align_items(iced::Alignment::Center)
+            );
+        }
+        scrollable(list).into()
+    }
+
+    fn view_calendars<'a>(&self) -> Element<'a, Message> {
+        let mut list = column![text(t!("calendars-title")).size(20)].spacing(5).width(200);
+        for calendar in &self.calendars {
+            let color = iced::Color::from_rgb8(calendar.color.0, calendar.color.1, calendar.color.2);
+            let file = calendar.file.clone();
+            let mut entry = column![
+                row![
+                    container(Space::new(12, 12)).style(theme::Container::Custom(Box::new(Swatch(color)))),
+                    checkbox(calendar.name.clone(), calendar.visible, move |visible| Message::CalendarVisible(file.clone(), visible)),
+                ]
+                .spacing(5)
+                .align_items(iced::Alignment::Center)
+            ]
+            .spacing(2);
+            if !calendar.is_main() {
+                let file = calendar.file.clone();
+                entry = entry.push(
+                    checkbox(t!("calendars-read-only"), calendar.read_only, move |read_only| Message::CalendarReadOnly(file.clone(), read_only))
+                        .size(12)
+                        .text_size(12)
+                );
+            }
+            if calendar.unreadable {
+                entry = entry.push(text(t!("calendars-unreadable")).size(12));
+            }
+            list = list.push(entry);
+        }
+        list = list.push(
+            row![
+                text_input(&t!("calendars-new-name"), &self.new_calendar)
+                    .on_input(Message::NewCalendarName)
+                    .on_submit(Message::AddCalendar),
+                button(text(t!("button-add"))).on_press(Message::AddCalendar),
+            ]
+            .spacing(5)
+        );
+        scrollable(list).into()
+    }
+
+    /// The alerts of the dialog, one editable row each
+    fn view_alerts<'a>(&self) -> Element<'a, Message> {
+        let alert_placeholder = t!("dialog-alert-placeholder", date = date_format::display_format().placeholder());
+        let mut alerts = column![text(t!("dialog-reminders")).size(12)].spacing(2);
+        for (index, alert) in self.dialog_appointment.alerts.iter().enumerate() {
+            alerts = alerts.push(
+                row![
+                    text_input(&alert_placeholder, alert)
+                        .on_input(move |string| Message::DialogAlert(index, string)),
+                    button(text(t!("button-remove")))
+                        .style(theme::Button::Secondary)
+                        .on_press(Message::DialogRemoveAlert(index)),
+                ]
+                .spacing(5)
+            );
+        }
+        alerts.push(button(text(t!("dialog-add-reminder"))).on_press(Message::DialogAddAlert)).into()
+    }
+
+    /// Known tags completing the tag typed last in the dialog
+    fn view_tag_suggestions<'a>(&self) -> Element<'a, Message> {
+        let entered: Vec<String> = self.dialog_appointment.tags.split(',')
+            .map(|tag| tag.trim().to_lowercase())
+            .collect();
+        let typed = entered.last().cloned().unwrap_or_default();
+        let mut suggestions = row![].spacing(5);
+        if typed.is_empty() {
+            return suggestions.into()
+        }
+        for (tag, _) in tag_counts(&self.appointments).into_iter()
+            .filter(|(tag, _)| tag.to_lowercase().starts_with(&typed) && !entered.contains(&tag.to_lowercase()))
+            .take(5)
+        {
+            suggestions = suggestions.push(
+                button(text(tag.clone()).size(12))
+                    .style(theme::Button::Secondary)
+                    .on_press(Message::DialogTagSuggestion(tag))
+            );
+        }
+        suggestions.into()
+    }
+
+    /// The calendar of the appointment, if there is more than one to choose from
+    fn view_calendar_picker<'a>(&self) -> Element<'a, Message> {
+        let writable: Vec<Calendar> = self.calendars.iter().filter(|x| x.is_writable()).cloned().collect();
+        let file = self.dialog_appointment.calendar.as_deref().unwrap_or(calendars::MAIN_FILE);
+        let selected = self.calendars.iter().find(|x| x.file == file).cloned();
+        if writable.len() < 2 || selected.as_ref().is_some_and(|x| !x.is_writable()) {
+            return Space::new(Length::Shrink, Length::Shrink).into()
+        }
+        column![
+            text(t!("dialog-calendar")).size(12),
+            PickList::new(writable, selected, Message::DialogCalendar)
+        ]
+        .into()
+    }
+
+    /// Delete, duplicate and move buttons, only shown when editing an existing appointment
+    fn view_dialog_actions<'a>(&self) -> Element<'a, Message> {
+        let Some(DialogOption::Edit(key, original)) = self.edit_dialog.clone() else {
+            return Space::new(Length::Shrink, Length::Shrink).into()
+        };
+        if self.is_read_only(&key) {
+            return button(text(t!("button-duplicate")))
+                .on_press(Message::DuplicateAppointment(key, original))
+                .into()
+        }
+        row![
+            button(text(t!("button-delete")))
+                .style(theme::Button::Destructive)
+                .on_press(Message::DeleteAppointment(key.clone(), original)),
+            button(text(t!("button-duplicate")))
+                .on_press(Message::DuplicateAppointment(key.clone(), original)),
+            button(text(t!("button-move-to-date")))
+                .on_press(Message::MoveAppointment(key, original)),
+        ]
+        .spacing(10)
+        .into()
+    }
+
+    fn view_scope_dialog<'a>(&self) -> Element<'a, Message> {
+        // A single occurrence can not be in another calendar than its series
+        let other_calendar = match (&self.edit_dialog, &self.pending_edit) {
+            (Some(DialogOption::Edit(key, _)), Some(edited)) => key.calendar != edited.calendar,
+            _ => false,
         };
-        if let Some(DialogOption::Edit(appointment)) = &self.edit_dialog {
-            let modal = container(
+        let this = button(text(t!("scope-this"))).width(Length::Fill);
+        container(
+            column![
+                text(t!("scope-question")),
+                if other_calendar { this } else { this.on_press(Message::DialogScope(EditScope::This)) },
+                button(text(t!("scope-following")))
+                    .width(Length::Fill)
+                    .on_press(Message::DialogScope(EditScope::Following)),
+                button(text(t!("scope-all")))
+                    .width(Length::Fill)
+                    .on_press(Message::DialogScope(EditScope::All)),
+                text(if other_calendar { t!("scope-other-calendar") } else { String::new() }).size(12),
+                button(text(t!("button-cancel")))
+                    .on_press(Message::DialogCancel),
+            ]
+            .spacing(10),
+        )
+        .width(300)
+        .padding(10)
+        .style(theme::Container::Custom(Box::new(Dialog)))
+        .into()
+    }
+
+    fn view_task_dialog<'a>(&self, id: Option<i32>) -> Element<'a, Message> {
+        let mut checklist = column![text(t!("task-checklist")).size(12)].spacing(5);
+        for (index, item) in self.dialog_task.checklist.iter().enumerate() {
+            checklist = checklist.push(
+                row![
+                    checkbox("", item.done, move |done| Message::TaskItemDone(index, done)),
+                    text_input("", &item.text)
+                        .on_input(move |string| Message::TaskItem(index, string)),
+                    button(text(t!("button-remove")))
+                        .style(theme::Button::Secondary)
+                        .on_press(Message::TaskRemoveItem(index)),
+                ]
+                .spacing(5)
+                .align_items(iced::Alignment::Center)
+            );
+        }
+        checklist = checklist.push(
+            button(text(t!("task-add-item")))
+                .style(theme::Button::Secondary)
+                .on_press(Message::TaskAddItem)
+        );
+        let mut actions = row![
+            button(text(t!("button-cancel")))
+                .on_press(Message::DialogCancel),
+            Space::new(Length::Fill, Length::Shrink),
+        ]
+        .spacing(5);
+        if let Some(id) = id {
+            actions = actions.push(
+                button(text(t!("button-delete")))
+                    .style(theme::Button::Destructive)
+                    .on_press(Message::DeleteTask(id))
+            );
+        }
+        actions = actions.push(
+            button(text(t!("button-submit")))
+                .on_press(Message::TaskSubmit)
+        );
+        container(
+            column![
                 column![
+                    text(t!("task-description")).size(12),
+                    text_input("", &self.dialog_task.description)
+                        .on_input(Message::TaskDescription)
+                        .on_submit(Message::TaskSubmit)
+                ],
+                column![
+                    text(t!("task-due")).size(12),
+                    text_input(&t!("task-due-placeholder", format = date_format::display_format().placeholder()), &self.dialog_task.due)
+                        .on_input(Message::TaskDue)
+                ],
+                column![
+                    text(t!("dialog-priority")).size(12),
+                    PickList::new(Priority::ALL, Some(self.dialog_task.priority), Message::TaskPriority)
+                ],
+                checklist,
+                actions,
+            ]
+            .spacing(20),
+        )
+        .width(400)
+        .padding(10)
+        .style(theme::Container::Custom(Box::new(Dialog)))
+        .into()
+    }
+
+    fn view_dialog<'a>(&self) -> Element<'a, Message> {
+        let read_only = matches!(&self.edit_dialog, Some(DialogOption::Edit(key, _)) if self.is_read_only(key));
+        let submit: Element<'a, Message> = if read_only {
+            text(t!("calendars-read-only-hint")).size(12).into()
+        } else {
+            button(text(t!("button-submit"))).on_press(Message::DialogSubmit).into()
+        };
+        let mut time = column![
+            checkbox(t!("dialog-all-day"), self.dialog_appointment.all_day, Message::DialogAllDay)
+        ]
+        .spacing(10);
+        let mut repeat = column![
+            checkbox(t!("dialog-repeat"), self.dialog_appointment.repeat, Message::DialogRepeat)
+        ]
+        .spacing(10);
+        if self.dialog_appointment.repeat {
+            repeat = repeat.push(
+                column![
+                    row![
+                        column![
+                            text(t!("dialog-frequency")).size(12),
+                            PickList::new(Frequency::ALL, Some(self.dialog_appointment.frequency), Message::DialogFrequency)
+                        ],
+                        column![
+                            text(t!("dialog-every")).size(12),
+                            text_input("1", self.dialog_appointment.interval.as_str())
+                                .on_input(Message::DialogInterval)
+                        ],
+                    ]
+                    .spacing(10),
                     column![
-                        text("Date").size(12),
-                        text_input("dd.mm.yyyy", self.dialog_appointment.date.as_str())
-                            .on_input(Message::DialogDate)
-                    ],
-                    column![
-                        text("Warning").size(12),
-                        text_input("dd.mm.yyyy", self.dialog_appointment.warning.as_str())
-                            .on_input(Message::DialogWarning)
-                    ],
-                    column![
-                        text("Tags").size(12),
-                        text_input("tag_1, tag_2", self.dialog_appointment.tags.as_str())
-                            .on_input(Message::DialogTags)
-                    ],
-                    column![
-                        text("Description").size(12),
-                        text_input("", self.dialog_appointment.description.as_str())
-                            .on_input(Message::DialogDescription)
-                    ],
-                    column![
-                        text("Priority").size(12),
-                        PickList::new(Priority::ALL, Some(self.dialog_appointment.priority), Message::DialogPriority)
+                        text(t!("dialog-on-days")).size(12),
+                        text_input(&t!("dialog-by-day-placeholder"), self.dialog_appointment.by_day.as_str())
+                            .on_input(Message::DialogByDay)
                     ],
                     row![
-                        button("Cancel")
-                            .on_press(Message::DialogCancel),
-                        Space::new(Length::Fill, Length::Shrink),
-                        button("Submit")
-                            .on_press(Message::DialogSubmit(Some(appointment.clone())))
-                        ]
+                        column![
+                            text(t!("dialog-count")).size(12),
+                            text_input("", self.dialog_appointment.count.as_str())
+                                .on_input(Message::DialogCount)
+                        ],
+                        column![
+                            text(t!("dialog-until")).size(12),
+                            text_input(date_format::display_format().placeholder(), self.dialog_appointment.until.as_str())
+                                .on_input(Message::DialogUntil)
+                        ],
+                    ]
+                    .spacing(10),
                 ]
-                .spacing(20),
-            )
-            .width(300)
-            .padding(10)
-            .style(theme::Container::Box);
-            
-            Modal::new(content, modal)
-                .on_blur(Message::DialogCancel)
-                .into()
-        } else if let Some(DialogOption::Add(_date)) = self.edit_dialog {
-            let modal = container(
-                column![
-                    column![
-                        text("Date").size(12),
-                        text_input("dd.mm.yyyy", self.dialog_appointment.date.as_str())
-                            .on_input(Message::DialogDate)
-                    ],
-                    column![
-                        text("Warning").size(12),
-                        text_input("dd.mm.yyyy", self.dialog_appointment.warning.as_str())
-                            .on_input(Message::DialogWarning)
-                    ],
-                    column![
-                        text("Tags").size(12),
-                        text_input("tag_1, tag_2",self.dialog_appointment.tags.as_str())
-                            .on_input(Message::DialogTags)
-                    ],
+                .spacing(10)
+            );
+        }
+        if !self.dialog_appointment.all_day {
+            time = time.push(
+                row![
                     column![
-                        text("Description").size(12),
-                        text_input("", self.dialog_appointment.description.as_str())
-                            .on_input(Message::DialogDescription)
+                        text(t!("dialog-start")).size(12),
+                        text_input("hh:mm", self.dialog_appointment.start.as_str())
+                            .on_input(Message::DialogStart)
                     ],
                     column![
-                        text("Priority").size(12),
-                        PickList::new(Priority::ALL, Some(self.dialog_appointment.priority), Message::DialogPriority)
+                        text(t!("dialog-end")).size(12),
+                        text_input(&t!("dialog-end-placeholder"), self.dialog_appointment.end.as_str())
+                            .on_input(Message::DialogEnd)
                     ],
-                    row![
-                        button("Cancel")
-                            .on_press(Message::DialogCancel),
-                        Space::new(Length::Fill, Length::Shrink),
-                        button("Submit")
-                            .on_press(Message::DialogSubmit(None))
-                        ]
                 ]
-                .spacing(20),
-            )
-            .width(300)
-            .padding(10)
-            .style(theme::Container::Box);
-            
-            Modal::new(content, modal)
-                .on_blur(Message::DialogCancel)
-                .into()
-        } else {
-            return content.into()
+                .spacing(10)
+            );
         }
+        container(
+            column![
+                column![
+                    text(t!("dialog-date")).size(12),
+                    text_input(date_format::display_format().placeholder(), self.dialog_appointment.date.as_str())
+                        .on_input(Message::DialogDate)
+                ],
+                time,
+                repeat,
+                self.view_alerts(),
+                column![
+                    text(t!("dialog-tags")).size(12),
+                    text_input("tag_1, tag_2", self.dialog_appointment.tags.as_str())
+                        .on_input(Message::DialogTags),
+                    self.view_tag_suggestions(),
+                ]
+                .spacing(2),
+                column![
+                    text(t!("dialog-description")).size(12),
+                    text_input("", self.dialog_appointment.description.as_str())
+                        .on_input(Message::DialogDescription)
+                ],
+                column![
+                    text(t!("dialog-priority")).size(12),
+                    PickList::new(Priority::ALL, Some(self.dialog_appointment.priority), Message::DialogPriority)
+                ],
+                self.view_calendar_picker(),
+                self.view_dialog_actions(),
+                row![
+                    button(text(t!("button-cancel")))
+                        .on_press(Message::DialogCancel),
+                    Space::new(Length::Fill, Length::Shrink),
+                    submit,
+                    ]
+            ]
+            .spacing(20),
+        )
+        .width(300)
+        .padding(10)
+        .style(theme::Container::Custom(Box::new(Dialog)))
+        .into()
     }
 
     fn view_week<'a>(&self, active_date: NaiveDateTime) -> Element<'a, Message> {
-        let mut content = row![].spacing(10).width(Length::Fill).height(Length::Fill);
+        let start = i18n::week_start(active_date.date());
+        let mut header = row![Space::with_width(HOUR_LABEL_WIDTH)].spacing(5);
+        let mut all_day = row![
+            container(text(t!("all-day")).size(12)).width(HOUR_LABEL_WIDTH)
+        ]
+        .spacing(5);
+        let mut grid = row![self.view_hour_labels()].spacing(5);
         for i in 0..7 {
-            let mut column = column![];
-            column = match i {
-                0 => column.push(text("Monday")),
-                1 => column.push(text("Tuesday")),
-                2 => column.push(text("Wednesday")),
-                3 => column.push(text("Thursday")),
-                4 => column.push(text("Friday")),
-                5 => column.push(text("Saturday")),
-                6 => column.push(text("Sunday")),
-                _ => column,
-            };
-            column = column.push(
-                container(scrollable(button("hello")))
+            let day = start.checked_add_days(Days::new(i)).unwrap();
+            header = header.push(self.drop_target(
+                button(
+                    text(format!("{} {}", i18n::weekday_name(day.weekday()), date::day_string(&day.and_time(NaiveTime::MIN))))
+                        .width(Length::Fill)
+                        .horizontal_alignment(Horizontal::Center)
+                )
+                    .width(Length::Fill)
+                    .padding(2)
+                    .style(DayContainer::styled(day_style(day, None)).move_to_style())
+                    .on_press(Message::AddAppointment(day.and_time(NaiveTime::MIN))),
+                DropSlot::Day(day),
+            ));
+            let mut occurrences = self.find_appointments_from_date(day.and_time(NaiveTime::MIN));
+            occurrences.sort_by_key(|occurrence| occurrence.appointment.sort_key());
+            let (whole_day, timed): (Vec<Occurrence>, Vec<Occurrence>) = occurrences.into_iter()
+                .partition(|occurrence| occurrence.appointment.all_day);
+            let mut strip = column![].spacing(2).width(Length::Fill);
+            for occurrence in whole_day {
+                strip = strip.push(self.appointment_chip(&occurrence, occurrence.appointment.description().clone()));
+            }
+            for task in task::due_on(&self.tasks, day) {
+                strip = strip.push(self.task_checkbox(task));
+            }
+            if let Some(ghost) = self.ghost(DropSlot::Day(day)) {
+                strip = strip.push(ghost);
+            }
+            all_day = all_day.push(self.drop_target(strip, DropSlot::Day(day)));
+            grid = grid.push(self.view_day_timeline(day, timed));
+        }
+        // The middle of the week lies in the same ISO week whichever day the week starts with
+        let week = (start + Days::new(3)).iso_week();
+        column![
+            text(t!("week-title", week = week.week(), year = week.year())).size(20),
+            header,
+            all_day,
+            scrollable(grid).height(Length::Fill),
+        ]
+        .spacing(5)
+        .into()
+    }
+
+    fn view_day<'a>(&self, active_date: NaiveDateTime) -> Element<'a, Message> {
+        let day = active_date.date();
+        let mut occurrences = self.find_appointments_from_date(day.and_time(NaiveTime::MIN));
+        occurrences.sort_by_key(|occurrence| occurrence.appointment.sort_key());
+
+        let mut details = column![].spacing(10);
+        for occurrence in &occurrences {
+            details = details.push(self.view_appointment_details(occurrence));
+        }
+        if occurrences.is_empty() {
+            details = details.push(text(t!("no-appointments")));
+        }
+
+        let mut all_day = column![].spacing(2);
+        for occurrence in occurrences.iter().filter(|occurrence| occurrence.appointment.all_day) {
+            all_day = all_day.push(self.appointment_chip(occurrence, occurrence.appointment.description().clone()));
+        }
+        for task in task::due_on(&self.tasks, day) {
+            all_day = all_day.push(self.task_checkbox(task));
+        }
+        let timed = occurrences.into_iter()
+            .filter(|occurrence| !occurrence.appointment.all_day)
+            .collect();
+
+        column![
+            text(date_format::format_long(day)).size(20),
+            row![
+                column![
+                    all_day,
+                    scrollable(row![self.view_hour_labels(), self.view_day_timeline(day, timed)].spacing(5))
+                        .height(Length::Fill),
+                ]
+                .spacing(5)
+                .width(Length::FillPortion(2)),
+                scrollable(details).width(Length::FillPortion(3)),
+            ]
+            .spacing(10),
+        ]
+        .spacing(5)
+        .into()
+    }
+
+    /// A card with everything known about an appointment, opening the edit dialog when pressed
+    fn view_appointment_details<'a>(&self, occurrence: &Occurrence) -> Element<'a, Message> {
+        let Occurrence { appointment, original } = occurrence;
+        let tags = appointment.tags.clone().unwrap_or_default()
+            .into_iter()
+            .filter(|tag| !tag.is_empty())
+            .collect::<Vec<String>>()
+            .join(", ");
+        let mut content = column![
+            text(appointment.time_string()).size(12),
+            text(appointment.description()),
+            text(t!("label-priority", priority = appointment.priority)).size(12),
+        ]
+        .spacing(5);
+        for alert in &appointment.alerts {
+            content = content.push(text(t!("label-reminder", alert = alert)).size(12));
+        }
+        if !tags.is_empty() {
+            content = content.push(text(t!("label-tags", tags = tags)).size(12));
+        }
+        if let Some(recurrence) = &self.appointments.iter().find(|x| x.key() == appointment.key()).and_then(|x| x.recurrence.clone()) {
+            content = content.push(text(t!("label-repeats", frequency = recurrence.frequency)).size(12));
+        }
+        mouse_area(
+            button(content)
+                .width(Length::Fill)
+                .padding(10)
+                .style(DayContainer::new().move_to_style())
+                .on_press(Message::EditAppointment(appointment.key(), *original))
+        )
+        .on_right_press(Message::ContextMenu(appointment.key(), *original))
+        .into()
+    }
+
+    fn view_hour_labels<'a>(&self) -> Element<'a, Message> {
+        let mut labels = column![].width(HOUR_LABEL_WIDTH);
+        for hour in 0..24 {
+            labels = labels.push(
+                container(text(format!("{:02}:00", hour)).size(12))
+                    .height(HOUR_HEIGHT)
             );
-            content = content.push(column);
         }
-        content.into()
-    }    
+        labels.into()
+    }
+
+    /// A column with one slot per hour and the timed appointments of `day` placed by their time
+    fn view_day_timeline<'a>(&self, day: NaiveDate, occurrences: Vec<Occurrence>) -> Element<'a, Message> {
+        let mut slots = column![].width(Length::Fill);
+        for hour in 0..24 {
+            let slot = DropSlot::Hour(day, hour);
+            let content = self.ghost(slot).unwrap_or(Space::new(Length::Fill, Length::Fill).into());
+            slots = slots.push(self.drop_target(
+                button(content)
+                    .width(Length::Fill)
+                    .height(HOUR_HEIGHT)
+                    .style(DayContainer::styled(DayContainerStyle::Slot).move_to_style())
+                    .on_press(Message::AddAppointment(day.and_hms_opt(hour, 0, 0).unwrap())),
+                slot,
+            ));
+        }
+
+        let mut lanes = row![].spacing(2).width(Length::Fill);
+        for lane in overlap_lanes(occurrences) {
+            let mut column = column![].width(Length::Fill);
+            let mut bottom = 0.0;
+            for occurrence in lane {
+                let (top, height) = timeline_position(&occurrence.appointment);
+                column = column
+                    .push(Space::with_height(top - bottom))
+                    .push(
+                        container(self.appointment_chip(&occurrence, occurrence.appointment.description().clone()))
+                            .height(height)
+                    );
+                bottom = top + height;
+            }
+            lanes = lanes.push(column);
+        }
+
+        let mut stack = Stack::new(slots).push(lanes);
+        if day == date::now().date() {
+            let now = chrono::Local::now().time();
+            stack = stack.push(
+                column![
+                    Space::with_height((now.hour() as f32 + now.minute() as f32 / 60.0) * HOUR_HEIGHT),
+                    container(Space::new(Length::Fill, 2))
+                        .style(theme::Container::Custom(Box::new(NowLine))),
+                ]
+            );
+        }
+        stack.into()
+    }
+
+    /// A button showing an appointment, opening the edit dialog or its context menu on right click
+    ///
+    /// It can be dragged to another day or hour to move it there, or to copy it while Ctrl is held.
+    fn appointment_chip<'a>(&self, occurrence: &Occurrence, label: String) -> Element<'a, Message> {
+        let Occurrence { appointment, original } = occurrence;
+        // The calendar is only marked once there is more than one
+        let color = self.appointments.iter()
+            .find(|x| x.key() == appointment.key())
+            .and_then(|series| self.calendar_of(series))
+            .filter(|_| self.calendars.len() > 1)
+            .map(|calendar| iced::Color::from_rgb8(calendar.color.0, calendar.color.1, calendar.color.2));
+        let chip = mouse_area(
+            Button::new(text(label).size(14))
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .padding(2)
+                .style(theme::Button::Custom(Box::new(Chip(appointment.priority, color))))
+                .on_press(Message::EditAppointment(appointment.key(), *original))
+        )
+        .on_right_press(Message::ContextMenu(appointment.key(), *original));
+        if self.is_read_only(&appointment.key()) {
+            return chip.into()
+        }
+        Draggable::new(chip, Message::DragAppointment(appointment.key(), *original)).into()
+    }
+
+    /// A checkbox checking off a task, opening the task dialog on right click
+    fn task_checkbox<'a>(&self, task: &Task) -> Element<'a, Message> {
+        let id = task.id;
+        mouse_area(
+            checkbox(task.label(), task.is_done(), move |done| Message::ToggleTask(id, done))
+                .size(14)
+                .text_size(14)
+        )
+        .on_right_press(Message::EditTask(id))
+        .into()
+    }
+
+    /// Makes `content` take the dragged appointment when it is dropped onto it
+    fn drop_target<'a>(&self, content: impl Into<Element<'a, Message>>, slot: DropSlot) -> Element<'a, Message> {
+        let target = DropTarget::new(content);
+        if self.drag.is_none() {
+            return target.into()
+        }
+        target
+            .on_enter(Message::DragOver(slot))
+            .on_drop(Message::DropAppointment(slot))
+            .into()
+    }
+
+    /// A preview of the dragged appointment if it is over `slot`
+    fn ghost<'a>(&self, slot: DropSlot) -> Option<Element<'a, Message>> {
+        let drag = self.drag.as_ref().filter(|drag| drag.over == Some(slot))?;
+        let appointment = &drag.occurrence.appointment;
+        let mut label = match slot {
+            DropSlot::Hour(_, hour) => format!("{:02}:00 {}", hour, appointment.description()),
+            DropSlot::Day(_) => appointment.description().clone(),
+        };
+        if self.modifiers.control() {
+            label = format!("+ {}", label);
+        }
+        Some(
+            container(text(label).size(14))
+                .width(Length::Fill)
+                .padding(2)
+                .style(theme::Container::Custom(Box::new(Ghost(appointment.priority))))
+                .into()
+        )
+    }
 
     fn view_year<'a>(&self, mut active_date: NaiveDateTime) -> Element<'a, Message> {
         active_date = NaiveDate::from_ymd_opt(active_date.year(), 1, 1)
@@ -363,129 +2110,149 @@ impl CalendarWidget{
     }
 
     fn year_month<'a>(&self, mut active_date: NaiveDateTime) -> (Element<'a, Message>, NaiveDateTime) {
-        let mut content =  column![].spacing(5).width(Length::Fill).height(Length::Fill);
-        content = match active_date.month() {
-            1 => content.push(text("January").horizontal_alignment(Horizontal::Center)),
-            2 => content.push(text("February").horizontal_alignment(Horizontal::Center)),
-            3 => content.push(text("March").horizontal_alignment(Horizontal::Center)),
-            4 => content.push(text("April").horizontal_alignment(Horizontal::Center)),
-            5 => content.push(text("May").horizontal_alignment(Horizontal::Center)),
-            6 => content.push(text("June").horizontal_alignment(Horizontal::Center)),
-            7 => content.push(text("July").horizontal_alignment(Horizontal::Center)),
-            8 => content.push(text("August").horizontal_alignment(Horizontal::Center)),
-            9 => content.push(text("September").horizontal_alignment(Horizontal::Center)),
-            10 => content.push(text("October").horizontal_alignment(Horizontal::Center)),
-            11 => content.push(text("November").horizontal_alignment(Horizontal::Center)),
-            12 => content.push(text("December").horizontal_alignment(Horizontal::Center)),
-            _ => content,
-        };
-        loop {
-            let mut row = row![].spacing(5).width(Length::Fill).height(Length::Fill);
+        let month = active_date.month();
+        let mut content = column![].spacing(2).width(Length::Fill).height(Length::Fill);
+        content = content.push(
+            text(i18n::month_name(month))
+                .width(Length::Fill)
+                .horizontal_alignment(Horizontal::Center)
+        );
+        let mut weekdays = row![].spacing(2).width(Length::Fill);
+        let mut weekday = i18n::first_weekday();
+        for _ in 0..7 {
+            weekdays = weekdays.push(text(i18n::weekday_short(weekday)).size(10).width(Length::Fill).horizontal_alignment(Horizontal::Center));
+            weekday = weekday.succ();
+        }
+        content = content.push(weekdays);
+        while active_date.month() == month {
+            let mut row = row![].spacing(2).width(Length::Fill).height(Length::Fill);
             for i in 0..7 {
-                if active_date.day() == 1 && active_date.weekday().num_days_from_monday() != i {
-                    row = row.push(container(text("")).width(Length::Fill).height(Length::Fill));
-                } else if active_date.checked_add_days(Days::new(1)).unwrap().day() == 1 {
+                if (active_date.day() == 1 && i18n::week_start(active_date.date()) + Days::new(i) != active_date.date()) ||
+                    month != active_date.month() {
                     row = row.push(container(text("")).width(Length::Fill).height(Length::Fill));
                 } else {
-                    let _appointments = self.find_appointments_from_date(active_date);
-                    row = row.push(button(text("")).style(DayContainer::new().move_to_style()).width(Length::Fill).height(Length::Fill));
+                    row = row.push(self.year_day(active_date));
                     active_date = active_date.checked_add_days(Days::new(1)).unwrap();
                 }
-
             }
             content = content.push(row);
-            if active_date.checked_add_days(Days::new(1)).unwrap().day() == 1 {
-                break
-            }
         }
-        (content.into(), active_date.checked_add_days(Days::new(1)).unwrap())
+        (content.into(), active_date)
     }
 
-    fn find_appointments_from_date(&self, active_date: NaiveDateTime) -> Vec<&Appointment> {
-        self.appointments.iter().filter(|x| x.date == date::naive_date_time_to_p_date(active_date)).collect()
-        
+    /// A day of the year view, colored by the number of appointments and their highest priority
+    fn year_day<'a>(&self, active_date: NaiveDateTime) -> Element<'a, Message> {
+        let mut occurrences = self.find_appointments_from_date(active_date);
+        occurrences.sort_by_key(|occurrence| occurrence.appointment.sort_key());
+        let priority = Priority::ALL.iter()
+            .copied()
+            .find(|priority| occurrences.iter().any(|occurrence| occurrence.appointment.priority == *priority));
+        let day = button(
+            text(active_date.day())
+                .size(12)
+                .width(Length::Fill)
+                .horizontal_alignment(Horizontal::Center)
+        )
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .padding(0)
+            .style(DayContainer::styled(DayContainerStyle::Heat(occurrences.len(), priority)).move_to_style())
+            .on_press(Message::JumpTo(active_date));
+        if occurrences.is_empty() {
+            return day.into()
+        }
+        let list = occurrences.iter()
+            .map(|Occurrence { appointment, .. }| match appointment.start_time() {
+                Some(start) => format!("{} {}", start.format("%H:%M"), appointment.description()),
+                None => appointment.description().clone(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        tooltip(day, list, tooltip::Position::FollowCursor)
+            .style(theme::Container::Box)
+            .into()
     }
 
-    fn view_month<'a>(&self, mut active_date: NaiveDateTime) -> Element<'a, Message> {
-        active_date = NaiveDate::from_ymd_opt(active_date.year(), active_date.month(), 1)
-            .unwrap()
-            .and_hms_opt(0, 0, 0)
-            .unwrap();
+    fn find_appointments_from_date(&self, active_date: NaiveDateTime) -> Vec<Occurrence> {
+        self.appointments.iter()
+            .filter(|x| self.calendar_of(x).is_none_or(|calendar| calendar.visible))
+            .flat_map(|x| x.occurrences_on(active_date.date()))
+            .filter(|occurrence| !occurrence.appointment.tag_list().any(|tag| self.hidden_tags.contains(tag)))
+            .collect()
+    }
+
+    fn view_month<'a>(&self, active_date: NaiveDateTime) -> Element<'a, Message> {
+        let first = NaiveDate::from_ymd_opt(active_date.year(), active_date.month(), 1).unwrap();
+        let month = first.month();
+        // Whole weeks are shown, starting with the week the first day of the month lies in
+        let mut day = i18n::week_start(first);
         let mut column = column![].spacing(5);
-        let month = active_date.month();
-        loop {
+        while day.month() == month || day < first {
             let mut row = row![].spacing(5).width(Length::Fill).height(Length::Fill);
-            for i in 0..7 {
-                dbg!(self.active_date);
-                if active_date.day() == 1 && active_date.weekday().num_days_from_monday() != i {
-                    row = row.push(container(text("")).width(Length::Fill).height(Length::Fill));
-                    println!("not a day");
-                } else if month != active_date.month(){ 
-                        row = row.push(container(text("")).width(Length::Fill).height(Length::Fill));
-                } else {
-                        let _appointments = self.find_appointments_from_date(active_date);
-                        row = row.push(self.make_container(active_date));
-                        active_date = active_date.checked_add_days(Days::new(1)).unwrap();
-                }
+            for _ in 0..7 {
+                row = row.push(self.make_container(day.and_time(NaiveTime::MIN), month));
+                day = day + Days::new(1);
             }
             column = column.push(row);
-        
-            if active_date.day() == 1 {
-                break
-            }
         }
         column.into()
     }
-    
 
-    fn make_container<'a>(&self, active_date: NaiveDateTime) -> Element<'a, Message> {
-        let appointments = self.find_appointments_from_date(active_date);
+    fn make_container<'a>(&self, active_date: NaiveDateTime, month: u32) -> Element<'a, Message> {
+        let mut occurrences = self.find_appointments_from_date(active_date);
+        occurrences.sort_by_key(|occurrence| occurrence.appointment.sort_key());
         let mut content = column![]
+            .spacing(2)
             .push(Text::new(date::naive_date_time_as_string(active_date)));
-        for appointment in appointments {
-            content = content.push(Button::new(iced::widget::text(appointment.description())).width(Length::Fill)
-                .on_press(Message::EditAppointment(appointment.id)))
-        }
-        // content = content.push(Button::new("+")
-        //     .width(Length::Fill)
-        //     .on_press(Message::AddAppointment(active_date))
-        // );
-        let container = Button::new(content)
-            .width(Length::Fill)
-            .height(Length::Fill)
-            .padding(5)
-            .style(DayContainer::new().move_to_style())
-            .on_press(Message::AddAppointment(active_date))
-            .into();
-    
-        container
+        for occurrence in &occurrences {
+            let appointment = &occurrence.appointment;
+            let label = match appointment.start_time() {
+                Some(start) => format!("{} {}", start.format("%H:%M"), appointment.description()),
+                None => appointment.description().clone(),
+            };
+            content = content.push(self.appointment_chip(occurrence, label));
+        }
+        for task in task::due_on(&self.tasks, active_date.date()) {
+            content = content.push(self.task_checkbox(task));
+        }
+        let slot = DropSlot::Day(active_date.date());
+        if let Some(ghost) = self.ghost(slot) {
+            content = content.push(ghost);
+        }
+        self.drop_target(
+            Button::new(content)
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .padding(5)
+                .style(DayContainer::styled(day_style(active_date.date(), Some(month))).move_to_style())
+                .on_press(Message::AddAppointment(active_date)),
+            slot,
+        )
     }
 
     pub fn handle_event(&mut self, event: iced_core::Event) -> Command<Message>{
         use iced_core::Event::*;
         match event {
-            Mouse(e) => {
-                if let iced::mouse::Event::WheelScrolled { delta} = e {
-                    if let ScrollDelta::Lines { x: _, y } = delta {
-                        if self.modifiers.control() {
-                            if y > 0.0 {
-                                self.depth = self.depth.depth_decrease();
-                            } else {
-                                self.depth = self.depth.depth_increase();
-                            }
-                        } else if y > 0.0 {
-                            let _ = self.update(Message::TimeDecrement);
-                        } else {
-                            let _ = self.update(Message::TimeIncrement);
-                        }
+            Mouse(iced::mouse::Event::WheelScrolled { delta: ScrollDelta::Lines { x: _, y } }) => {
+                if self.modifiers.control() {
+                    if y > 0.0 {
+                        self.depth = self.depth.depth_decrease();
+                    } else {
+                        self.depth = self.depth.depth_increase();
                     }
+                } else if y > 0.0 {
+                    let _ = self.update(Message::TimeDecrement);
+                } else {
+                    let _ = self.update(Message::TimeIncrement);
                 }
             }
-            Window(e) => {                                   
-                if let iced::window::Event::CloseRequested = e {    
-                    save_appointments(self.appointments.clone());   
-                    return window::close()                          
-                }
+            // Released anywhere but on a drop target
+            Mouse(iced::mouse::Event::ButtonReleased(iced::mouse::Button::Left)) => {
+                self.drag = None;
+            }
+            Window(iced::window::Event::CloseRequested) => {
+                self.save();
+                return window::close()
             }
             Keyboard(e) => {
                 match e {
@@ -500,6 +2267,23 @@ impl CalendarWidget{
                         modifiers: _ 
                     } => {
                         self.edit_dialog = None;
+                        self.quick_add = None;
+                        self.drag = None;
+                    }
+                    iced_core::keyboard::Event::KeyPressed { 
+                        key_code: KeyCode::N,
+                        modifiers,
+                    } if modifiers.command() => {
+                        return self.update(Message::QuickAdd)
+                    }
+                    iced_core::keyboard::Event::KeyPressed { 
+                        key_code: KeyCode::Z,
+                        modifiers,
+                    } if modifiers.command() => {
+                        if modifiers.shift() {
+                            return self.update(Message::Redo)
+                        }
+                        return self.update(Message::Undo)
                     }
                     iced_core::keyboard::Event::KeyPressed { 
                         key_code: KeyCode::Down, 
@@ -543,6 +2327,17 @@ impl CalendarWidget{
 enum DayContainerStyle {
     #[default]
     Bordered,
+    /// An hour in the timeline of a day
+    Slot,
+    /// The current day
+    Today,
+    /// A day before today
+    Past,
+    Weekend,
+    /// A day of the previous or next month shown to fill up the weeks of the month view
+    OutsideMonth,
+    /// A day of the year view with its number of appointments and their highest priority
+    Heat(usize, Option<Priority>),
 }
 
 pub struct DayContainer(DayContainerStyle);
@@ -558,6 +2353,10 @@ impl DayContainer {
         Self::default()
     }
 
+    fn styled(style: DayContainerStyle) -> Self {
+        Self(style)
+    }
+
     pub fn move_to_style(self) -> iced::theme::Button {
         self.into()
     }
@@ -573,57 +2372,368 @@ impl iced::widget::button::StyleSheet for DayContainer {
     type Style = iced::theme::Theme;
 
     fn active(&self, style: &Self::Style) -> Appearance {
-        Appearance { 
+        let palette = style.extended_palette();
+        let base = Appearance {
+            shadow_offset: Vector::new(0.0, 0.0),
+            text_color: palette.background.base.text,
+            background: Some(iced::Color::TRANSPARENT.into()),
+            border_radius: 6.0.into(),
+            border_width: 2.0,
+            border_color: palette.background.strong.color,
+        };
+        match self.0 {
+            DayContainerStyle::Bordered => base,
+            DayContainerStyle::Slot => Appearance {
+                border_radius: 0.0.into(),
+                border_width: 0.5,
+                border_color: palette.background.weak.color,
+                ..base
+            },
+            DayContainerStyle::Today => Appearance {
+                background: Some(iced::Color {a: 0.1, ..palette.primary.base.color}.into()),
+                border_width: 2.5,
+                border_color: palette.primary.base.color,
+                ..base
+            },
+            DayContainerStyle::Past => Appearance {
+                text_color: iced::Color {a: 0.6, ..palette.background.base.text},
+                border_color: palette.background.weak.color,
+                ..base
+            },
+            DayContainerStyle::Weekend => Appearance {
+                background: Some(palette.background.weak.color.into()),
+                ..base
+            },
+            DayContainerStyle::OutsideMonth => Appearance {
+                text_color: iced::Color {a: 0.4, ..palette.background.base.text},
+                border_width: 1.0,
+                border_color: palette.background.weak.color,
+                ..base
+            },
+            DayContainerStyle::Heat(count, priority) => {
+                let color = match priority {
+                    Some(Priority::High) => palette.danger.base.color,
+                    Some(Priority::Middle) => palette.primary.base.color,
+                    Some(Priority::Low) => palette.success.base.color,
+                    None => iced::Color::TRANSPARENT,
+                };
+                // More appointments make the day stand out more, the scale stops at four
+                let alpha = 0.25 + 0.75 * (count.min(4) as f32 / 4.0);
+                Appearance {
+                    background: Some(iced::Color { a: color.a * alpha, ..color }.into()),
+                    border_radius: 3.0.into(),
+                    border_width: 0.5,
+                    ..base
+                }
+            },
+        }
+    }
+}
+
+/// The style of a day cell, `month` is the month shown by the view if it only shows one
+fn day_style(day: NaiveDate, month: Option<u32>) -> DayContainerStyle {
+    let today = date::now().date();
+    if month.is_some_and(|month| month != day.month()) {
+        DayContainerStyle::OutsideMonth
+    } else if day == today {
+        DayContainerStyle::Today
+    } else if day < today {
+        DayContainerStyle::Past
+    } else if day.weekday().num_days_from_monday() >= 5 {
+        DayContainerStyle::Weekend
+    } else {
+        DayContainerStyle::Bordered
+    }
+}
+
+/// An appointment chip colored by its priority, with a border in the color of its calendar
+struct Chip(Priority, Option<iced::Color>);
+
+impl Chip {
+    fn pair(&self, style: &iced::theme::Theme) -> iced::theme::palette::Pair {
+        let palette = style.extended_palette();
+        match self.0 {
+            Priority::High => palette.danger.base,
+            Priority::Middle => palette.primary.base,
+            Priority::Low => palette.success.base,
+        }
+    }
+}
+
+impl iced::widget::button::StyleSheet for Chip {
+    type Style = iced::theme::Theme;
+
+    fn active(&self, style: &Self::Style) -> Appearance {
+        let pair = self.pair(style);
+        Appearance {
             shadow_offset: Vector::new(0.0, 0.0),
-            text_color: Some(style.palette().text).unwrap(),
-            background: Some(iced::Color::TRANSPARENT.into()), 
-            border_radius: 6.0.into(), 
-            border_width: 2.0.into(), 
-            border_color: iced::Color {a: 0.5, ..style.palette().text} 
+            text_color: pair.text,
+            background: Some(iced::Color {a: 0.8, ..pair.color}.into()),
+            border_radius: 4.0.into(),
+            border_width: if self.1.is_some() { 2.0 } else { 0.0 },
+            border_color: self.1.unwrap_or(iced::Color::TRANSPARENT),
+        }
+    }
+
+    fn hovered(&self, style: &Self::Style) -> Appearance {
+        Appearance {
+            background: Some(self.pair(style).color.into()),
+            ..self.active(style)
+        }
+    }
+}
+
+/// A small square in the color of a calendar
+struct Swatch(iced::Color);
+
+impl iced::widget::container::StyleSheet for Swatch {
+    type Style = iced::theme::Theme;
+
+    fn appearance(&self, _style: &Self::Style) -> iced::widget::container::Appearance {
+        iced::widget::container::Appearance {
+            background: Some(self.0.into()),
+            border_radius: 3.0.into(),
+            ..Default::default()
+        }
+    }
+}
+
+/// The box of a dialog shown over the calendar, opaque so the backdrop does not shine through
+struct Dialog;
+
+impl iced::widget::container::StyleSheet for Dialog {
+    type Style = iced::theme::Theme;
+
+    fn appearance(&self, style: &Self::Style) -> iced::widget::container::Appearance {
+        let palette = style.extended_palette();
+        iced::widget::container::Appearance {
+            text_color: Some(palette.background.base.text),
+            background: Some(palette.background.base.color.into()),
+            border_radius: 6.0.into(),
+            border_width: 1.0,
+            border_color: palette.background.strong.color,
+        }
+    }
+}
+
+/// Preview of a dragged appointment in the day or hour it would be dropped on
+struct Ghost(Priority);
+
+impl iced::widget::container::StyleSheet for Ghost {
+    type Style = iced::theme::Theme;
+
+    fn appearance(&self, style: &Self::Style) -> iced::widget::container::Appearance {
+        let pair = Chip(self.0, None).pair(style);
+        iced::widget::container::Appearance {
+            text_color: Some(style.extended_palette().background.base.text),
+            background: Some(iced::Color {a: 0.3, ..pair.color}.into()),
+            border_radius: 4.0.into(),
+            border_width: 1.5,
+            border_color: pair.color,
+        }
+    }
+}
+
+/// The line marking the current time in the timeline of today
+struct NowLine;
+
+impl iced::widget::container::StyleSheet for NowLine {
+    type Style = iced::theme::Theme;
+
+    fn appearance(&self, style: &Self::Style) -> iced::widget::container::Appearance {
+        iced::widget::container::Appearance {
+            background: Some(style.palette().danger.into()),
+            ..Default::default()
+        }
+    }
+}
+
+/// Id of the text input of the quick-add bar
+const QUICK_ADD: &str = "quick-add";
+
+/// Height of an hour in the timeline of a day
+const HOUR_HEIGHT: f32 = 48.0;
+const HOUR_LABEL_WIDTH: f32 = 50.0;
+
+/// Top offset and height of a timed appointment in the timeline of its day
+fn timeline_position(appointment: &Appointment) -> (f32, f32) {
+    let minutes = |time: Option<NaiveTime>| time.map(|time| time.hour() * 60 + time.minute()).unwrap_or(0) as f32;
+    let top = minutes(appointment.start_time()) / 60.0 * HOUR_HEIGHT;
+    let height = (minutes(appointment.end_time()) - minutes(appointment.start_time())) / 60.0 * HOUR_HEIGHT;
+    (top, height.max(HOUR_HEIGHT / 2.0))
+}
+
+/// Distributes the appointments, sorted by start, into lanes without overlapping appointments
+fn overlap_lanes(occurrences: Vec<Occurrence>) -> Vec<Vec<Occurrence>> {
+    let mut lanes: Vec<Vec<Occurrence>> = vec![];
+    for occurrence in occurrences {
+        let (top, _) = timeline_position(&occurrence.appointment);
+        let free = lanes.iter_mut().find(|lane| {
+            let (last_top, last_height) = timeline_position(&lane.last().unwrap().appointment);
+            last_top + last_height <= top
+        });
+        match free {
+            Some(lane) => lane.push(occurrence),
+            None => lanes.push(vec![occurrence]),
         }
     }
+    lanes
+}
+
+/// The saved data, with the reason if it could not be loaded
+fn load() -> (storage::SavedData, Option<String>) {
+    match storage::load(&storage::data_file()) {
+        Ok(data) => (data, None),
+        Err(error) => (storage::SavedData::new(vec![], vec![], vec![]), Some(error.to_string())),
+    }
+}
+
+/// The calendars with the appointments of all but the main one added to `appointments`
+///
+/// Also returns why calendars could not be read, those are not written until the next start.
+fn load_calendars(appointments: &mut Vec<Appointment>) -> (Vec<Calendar>, Option<String>) {
+    let mut errors = vec![];
+    let mut calendars = calendars::load(&calendars::calendars_file()).unwrap_or_else(|error| {
+        errors.push(t!("calendars-load-failed", name = "calendars.yml", error = error));
+        vec![Calendar::default()]
+    });
+    for (name, error) in calendars::load_appointments(&mut calendars, appointments) {
+        errors.push(t!("calendars-load-failed", name = name, error = error));
+    }
+    (calendars, (!errors.is_empty()).then(|| errors.join("; ")))
 }
 
 fn valid_date(string: String) -> Result<PDate, String> {
-    let time: Vec<&str> = string.split(".").collect();
-    if time.len() != 3 {
-        return Err(string)
+    match date_format::parse_date(&string, date::now().date()) {
+        Some(day) => Ok(date::naive_date_time_to_p_date(day.and_time(NaiveTime::MIN))),
+        None => Err(string),
     }
-    let day = time[0].parse::<u32>();
-    let month = time[1].parse::<u32>();
-    let year = time[2].parse::<i32>();
-    if day.is_err() | month.is_err() | year.is_err() {
-        return Err(string)
-    } else if time[2].as_bytes().len() != 4 {
+}
+
+/// Parses a time of day written as `hh:mm` or `hh`
+fn valid_time(string: String) -> Result<NaiveTime, String> {
+    let time: Vec<&str> = string.trim().split(':').collect();
+    let hour = time[0].parse::<u32>();
+    let min = match time.len() {
+        1 => Ok(0),
+        2 => time[1].parse::<u32>(),
+        _ => return Err(string)
+    };
+    match (hour, min) {
+        (Ok(hour), Ok(min)) => NaiveTime::from_hms_opt(hour, min, 0).ok_or(string),
+        _ => Err(string)
+    }
+}
+
+/// Parses a duration written as `+1h30m`, `+90m` or `+2h`
+fn valid_duration(string: String) -> Result<Duration, String> {
+    let Some(rest) = string.trim().strip_prefix('+') else {
         return Err(string)
-    } else if NaiveDate::from_ymd_opt(year.clone().unwrap(), month.clone().unwrap(), day.clone().unwrap()).is_none() {
+    };
+    let mut minutes: i64 = 0;
+    let mut number = String::new();
+    for c in rest.chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            'h' | 'm' if !number.is_empty() => {
+                let value = number.parse::<i64>().ok()
+                    .and_then(|value| value.checked_mul(if c == 'h' { 60 } else { 1 }))
+                    .and_then(|value| minutes.checked_add(value));
+                minutes = value.ok_or(string.clone())?;
+                number.clear();
+            }
+            _ => return Err(string)
+        }
+    }
+    if !number.is_empty() || minutes == 0 {
         return Err(string)
+    }
+    Duration::try_minutes(minutes).ok_or(string)
+}
+
+/// Parses the end of an appointment, either as a time of day or as a duration after `start`
+fn valid_end(start: NaiveTime, string: String) -> Result<NaiveTime, String> {
+    let end = if string.trim().starts_with('+') {
+        let (end, overflow) = start.overflowing_add_signed(valid_duration(string.clone())?);
+        if overflow != 0 {
+            return Err(string)
+        }
+        end
     } else {
-        return Ok(PDate::new(year.unwrap(), month.unwrap(), day.unwrap(), 0, 0, 0))
+        valid_time(string.clone())?
+    };
+    if end < start {
+        return Err(string)
     }
+    Ok(end)
+}
 
+/// Parses an alert written as a date, optionally followed by `hh:mm`,
+/// or as an offset to the start like `-P1D` or `-PT15M`
+fn valid_alert(string: String) -> Result<Alert, String> {
+    let trimmed = string.trim();
+    if let Some(offset) = date::parse_iso_duration(trimmed) {
+        return Ok(Alert::Relative(offset))
+    }
+    // The date itself may contain spaces, like "next fri 9:00"
+    let (day, time) = match (valid_date(trimmed.to_string()), trimmed.rsplit_once(' ')) {
+        (Ok(day), _) => (day, NaiveTime::MIN),
+        (Err(_), Some((day, time))) => (
+            valid_date(day.to_string()).map_err(|_| string.clone())?,
+            valid_time(time.to_string()).map_err(|_| string.clone())?,
+        ),
+        (Err(_), None) => return Err(string),
+    };
+    Ok(Alert::Absolute(PDate { hour: time.hour(), min: time.minute(), ..day }))
 }
 
 fn valid_tags(string: String) -> Result<Vec<String>, String> {
-    let tags: Vec<&str> = string.split(",").collect();
+    let tags: Vec<&str> = string.split(',').collect();
     let mut tags_string: Vec<String> = vec![];
     for mut tag in tags {
         tag = tag.trim();
         tags_string.push(tag.to_string())
     }
-    return Ok(tags_string)
+    Ok(tags_string)
 }
 
-fn new_id(appointments: Vec<Appointment>) -> i32 {
-    let mut ids: Vec<i32> = vec![];
-    for appointment in appointments {
-        ids.push(appointment.id);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(hour: u32, min: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, min, 0).unwrap()
     }
-    ids.sort();
-    for i in 0..ids.len() {
-        if i as i32 != ids[i] {
-            return i as i32
-        }
+
+    #[test]
+    fn parses_times_and_durations() {
+        assert_eq!(valid_time("9".to_string()), Ok(time(9, 0)));
+        assert_eq!(valid_time(" 14:30 ".to_string()), Ok(time(14, 30)));
+        assert!(valid_time("24:00".to_string()).is_err());
+        assert!(valid_time("9:61".to_string()).is_err());
+        assert!(valid_time("9:00:00".to_string()).is_err());
+        assert!(valid_time("noon".to_string()).is_err());
+
+        assert_eq!(valid_duration("+1h30m".to_string()), Ok(Duration::minutes(90)));
+        assert_eq!(valid_duration("+90m".to_string()), Ok(Duration::minutes(90)));
+        assert_eq!(valid_duration("+2h".to_string()), Ok(Duration::hours(2)));
+        assert!(valid_duration("1h".to_string()).is_err());
+        assert!(valid_duration("+0m".to_string()).is_err());
+        assert!(valid_duration("+1h30".to_string()).is_err());
+        assert!(valid_duration("+h".to_string()).is_err());
+        assert!(valid_duration("+999999999999999m".to_string()).is_err());
+        assert!(valid_duration("+99999999999999999h".to_string()).is_err());
+        assert!(valid_duration("+9223372036854775807m1m".to_string()).is_err());
+        assert!(valid_end(NaiveTime::MIN, "+999999999999999m".to_string()).is_err());
+    }
+
+    #[test]
+    fn parses_ends() {
+        assert_eq!(valid_end(time(9, 0), "10:15".to_string()), Ok(time(10, 15)));
+        assert_eq!(valid_end(time(9, 0), "+45m".to_string()), Ok(time(9, 45)));
+        assert_eq!(valid_end(time(9, 0), "9:00".to_string()), Ok(time(9, 0)));
+        // Appointments do not end before they start or on the next day
+        assert!(valid_end(time(9, 0), "8:59".to_string()).is_err());
+        assert!(valid_end(time(23, 0), "+2h".to_string()).is_err());
     }
-    return ids.len() as i32
 }
@@ -0,0 +1,399 @@
+use iced::advanced::layout::{self, Layout};
+use iced::advanced::overlay;
+use iced::advanced::renderer;
+use iced::advanced::widget::{self, tree, Widget};
+use iced::advanced::{self, Clipboard, Shell};
+use iced::event;
+use iced::mouse;
+use iced::{Element, Event, Length, Point, Rectangle};
+
+/// How far the mouse has to move with the button held before a drag starts
+const DRAG_DISTANCE: f32 = 5.0;
+
+/// A widget whose content can be picked up with the mouse
+///
+/// Moving the mouse a few pixels with the left button held publishes `on_drag`,
+/// a click without moving reaches the content as usual.
+/// Where the content is dropped is up to the [`DropTarget`]s below the cursor.
+pub struct Draggable<'a, Message, Renderer> {
+    content: Element<'a, Message, Renderer>,
+    on_drag: Message,
+}
+
+impl<'a, Message, Renderer> Draggable<'a, Message, Renderer> {
+    pub fn new(content: impl Into<Element<'a, Message, Renderer>>, on_drag: Message) -> Self {
+        Self {
+            content: content.into(),
+            on_drag,
+        }
+    }
+}
+
+#[derive(Default)]
+struct DragState {
+    /// Where the left button was pressed on the content
+    pressed_at: Option<Point>,
+    dragging: bool,
+}
+
+impl<'a, Message, Renderer> Widget<Message, Renderer>
+    for Draggable<'a, Message, Renderer>
+where
+    Renderer: advanced::Renderer,
+    Message: Clone,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<DragState>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(DragState::default())
+    }
+
+    fn children(&self) -> Vec<widget::Tree> {
+        vec![widget::Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut widget::Tree) {
+        tree.diff_children(std::slice::from_ref(&self.content));
+    }
+
+    fn width(&self) -> Length {
+        self.content.as_widget().width()
+    }
+
+    fn height(&self) -> Length {
+        self.content.as_widget().height()
+    }
+
+    fn layout(
+        &self,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        self.content.as_widget().layout(renderer, limits)
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut widget::Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) -> event::Status {
+        let state = tree.state.downcast_mut::<DragState>();
+        let mut cursor = cursor;
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                state.pressed_at = cursor.position_over(layout.bounds());
+            }
+            Event::Mouse(mouse::Event::CursorMoved { position }) => {
+                if state.dragging {
+                    return event::Status::Ignored;
+                }
+                if let Some(origin) = state.pressed_at {
+                    if origin.distance(position) > DRAG_DISTANCE {
+                        state.dragging = true;
+                        shell.publish(self.on_drag.clone());
+                        return event::Status::Captured;
+                    }
+                }
+            }
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                state.pressed_at = None;
+                if state.dragging {
+                    state.dragging = false;
+                    // The content only gets to reset itself, a button must not take the drop for a click
+                    cursor = mouse::Cursor::Unavailable;
+                    let _ = self.content.as_widget_mut().on_event(
+                        &mut tree.children[0],
+                        event,
+                        layout,
+                        cursor,
+                        renderer,
+                        clipboard,
+                        shell,
+                        viewport,
+                    );
+                    return event::Status::Ignored;
+                }
+            }
+            _ => {}
+        }
+        self.content.as_widget_mut().on_event(
+            &mut tree.children[0],
+            event,
+            layout,
+            cursor,
+            renderer,
+            clipboard,
+            shell,
+            viewport,
+        )
+    }
+
+    fn draw(
+        &self,
+        tree: &widget::Tree,
+        renderer: &mut Renderer,
+        theme: &<Renderer as advanced::Renderer>::Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor,
+            viewport,
+        );
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &widget::Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        if tree.state.downcast_ref::<DragState>().dragging {
+            return mouse::Interaction::Grabbing;
+        }
+        self.content.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout,
+            cursor,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn operate(
+        &self,
+        tree: &mut widget::Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn widget::Operation<Message>,
+    ) {
+        self.content.as_widget().operate(&mut tree.children[0], layout, renderer, operation);
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut widget::Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+    ) -> Option<overlay::Element<'b, Message, Renderer>> {
+        self.content.as_widget_mut().overlay(&mut tree.children[0], layout, renderer)
+    }
+}
+
+impl<'a, Message, Renderer> From<Draggable<'a, Message, Renderer>>
+    for Element<'a, Message, Renderer>
+where
+    Renderer: 'a + advanced::Renderer,
+    Message: 'a + Clone,
+{
+    fn from(draggable: Draggable<'a, Message, Renderer>) -> Self {
+        Element::new(draggable)
+    }
+}
+
+/// A widget that takes what is dragged onto it
+///
+/// It only reacts while a drop message is set, publishing `on_enter` when the cursor
+/// moves onto it and `on_drop` when the mouse button is released over it.
+pub struct DropTarget<'a, Message, Renderer> {
+    content: Element<'a, Message, Renderer>,
+    on_enter: Option<Message>,
+    on_drop: Option<Message>,
+}
+
+impl<'a, Message, Renderer> DropTarget<'a, Message, Renderer> {
+    pub fn new(content: impl Into<Element<'a, Message, Renderer>>) -> Self {
+        Self {
+            content: content.into(),
+            on_enter: None,
+            on_drop: None,
+        }
+    }
+
+    /// Sets the message published when something is dragged onto the target
+    pub fn on_enter(mut self, message: Message) -> Self {
+        self.on_enter = Some(message);
+        self
+    }
+
+    /// Sets the message published when something is dropped, without it the target stays inactive
+    pub fn on_drop(mut self, message: Message) -> Self {
+        self.on_drop = Some(message);
+        self
+    }
+}
+
+#[derive(Default)]
+struct DropState {
+    hovered: bool,
+}
+
+impl<'a, Message, Renderer> Widget<Message, Renderer>
+    for DropTarget<'a, Message, Renderer>
+where
+    Renderer: advanced::Renderer,
+    Message: Clone,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<DropState>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(DropState::default())
+    }
+
+    fn children(&self) -> Vec<widget::Tree> {
+        vec![widget::Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut widget::Tree) {
+        tree.diff_children(std::slice::from_ref(&self.content));
+    }
+
+    fn width(&self) -> Length {
+        self.content.as_widget().width()
+    }
+
+    fn height(&self) -> Length {
+        self.content.as_widget().height()
+    }
+
+    fn layout(
+        &self,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        self.content.as_widget().layout(renderer, limits)
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut widget::Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) -> event::Status {
+        let status = self.content.as_widget_mut().on_event(
+            &mut tree.children[0],
+            event.clone(),
+            layout,
+            cursor,
+            renderer,
+            clipboard,
+            shell,
+            viewport,
+        );
+        let state = tree.state.downcast_mut::<DropState>();
+        let Some(on_drop) = &self.on_drop else {
+            state.hovered = false;
+            return status;
+        };
+        let over = cursor.is_over(layout.bounds());
+        match event {
+            Event::Mouse(mouse::Event::CursorMoved { .. }) => {
+                if over && !state.hovered {
+                    if let Some(on_enter) = &self.on_enter {
+                        shell.publish(on_enter.clone());
+                    }
+                }
+                state.hovered = over;
+            }
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) if over => {
+                state.hovered = false;
+                shell.publish(on_drop.clone());
+                return event::Status::Captured;
+            }
+            _ => {}
+        }
+        status
+    }
+
+    fn draw(
+        &self,
+        tree: &widget::Tree,
+        renderer: &mut Renderer,
+        theme: &<Renderer as advanced::Renderer>::Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor,
+            viewport,
+        );
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &widget::Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout,
+            cursor,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn operate(
+        &self,
+        tree: &mut widget::Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn widget::Operation<Message>,
+    ) {
+        self.content.as_widget().operate(&mut tree.children[0], layout, renderer, operation);
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut widget::Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+    ) -> Option<overlay::Element<'b, Message, Renderer>> {
+        self.content.as_widget_mut().overlay(&mut tree.children[0], layout, renderer)
+    }
+}
+
+impl<'a, Message, Renderer> From<DropTarget<'a, Message, Renderer>>
+    for Element<'a, Message, Renderer>
+where
+    Renderer: 'a + advanced::Renderer,
+    Message: 'a + Clone,
+{
+    fn from(target: DropTarget<'a, Message, Renderer>) -> Self {
+        Element::new(target)
+    }
+}
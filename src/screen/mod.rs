@@ -1,2 +1,8 @@
+pub mod agenda;
 pub mod calendar;
+pub mod drag;
 pub mod modal_overlay;
+pub mod search;
+pub mod settings;
+pub mod stack;
+pub mod tasks;
\ No newline at end of file
@@ -8,14 +8,45 @@ use iced::event;
 use iced::mouse;
 use iced::{Color, Element, Event, Length, Point, Rectangle, Size};
 
+/// The look of a [`Modal`]
+pub trait StyleSheet {
+    type Style: Default;
+
+    /// Color laid over the base element behind the modal
+    fn backdrop(&self, style: &Self::Style) -> Color;
+}
+
+impl StyleSheet for iced::Theme {
+    type Style = ();
+
+    fn backdrop(&self, _style: &Self::Style) -> Color {
+        let background = self.palette().background;
+        // Light palettes only need a lighter shade to set the modal apart
+        let dark = background.r + background.g + background.b < 1.5;
+        Color {
+            a: if dark { 0.8 } else { 0.5 },
+            ..Color::BLACK
+        }
+    }
+}
+
 /// A widget that centers a modal element over some base element
-pub struct Modal<'a, Message, Renderer> {
+pub struct Modal<'a, Message, Renderer>
+where
+    Renderer: advanced::Renderer,
+    Renderer::Theme: StyleSheet,
+{
     base: Element<'a, Message, Renderer>,
     modal: Element<'a, Message, Renderer>,
     on_blur: Option<Message>,
+    style: <Renderer::Theme as StyleSheet>::Style,
 }
 
-impl<'a, Message, Renderer> Modal<'a, Message, Renderer> {
+impl<'a, Message, Renderer> Modal<'a, Message, Renderer>
+where
+    Renderer: advanced::Renderer,
+    Renderer::Theme: StyleSheet,
+{
     /// Returns a new [`Modal`]
     pub fn new(
         base: impl Into<Element<'a, Message, Renderer>>,
@@ -25,6 +56,7 @@ impl<'a, Message, Renderer> Modal<'a, Message, Renderer> {
             base: base.into(),
             modal: modal.into(),
             on_blur: None,
+            style: Default::default(),
         }
     }
 
@@ -42,6 +74,7 @@ impl<'a, Message, Renderer> Widget<Message, Renderer>
     for Modal<'a, Message, Renderer>
 where
     Renderer: advanced::Renderer,
+    Renderer::Theme: StyleSheet,
     Message: Clone,
 {
     fn children(&self) -> Vec<widget::Tree> {
@@ -128,6 +161,7 @@ where
                 tree: &mut state.children[1],
                 size: layout.bounds().size(),
                 on_blur: self.on_blur.clone(),
+                style: &self.style,
             }),
         ))
     }
@@ -165,17 +199,23 @@ where
     }
 }
 
-struct Overlay<'a, 'b, Message, Renderer> {
+struct Overlay<'a, 'b, Message, Renderer>
+where
+    Renderer: advanced::Renderer,
+    Renderer::Theme: StyleSheet,
+{
     content: &'b mut Element<'a, Message, Renderer>,
     tree: &'b mut widget::Tree,
     size: Size,
     on_blur: Option<Message>,
+    style: &'b <Renderer::Theme as StyleSheet>::Style,
 }
 
 impl<'a, 'b, Message, Renderer> overlay::Overlay<Message, Renderer>
     for Overlay<'a, 'b, Message, Renderer>
 where
     Renderer: advanced::Renderer,
+    Renderer::Theme: StyleSheet,
     Message: Clone,
 {
     fn layout(
@@ -247,10 +287,7 @@ where
                 border_width: 0.0,
                 border_color: Color::TRANSPARENT,
             },
-            Color {
-                a: 0.80,
-                ..Color::BLACK
-            },
+            theme.backdrop(self.style),
         );
 
         self.content.as_widget().draw(
@@ -311,6 +348,7 @@ impl<'a, Message, Renderer> From<Modal<'a, Message, Renderer>>
     for Element<'a, Message, Renderer>
 where
     Renderer: 'a + advanced::Renderer,
+    Renderer::Theme: StyleSheet,
     Message: 'a + Clone,
 {
     fn from(modal: Modal<'a, Message, Renderer>) -> Self {
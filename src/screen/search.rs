@@ -0,0 +1,79 @@
+use iced::widget::{button, column, mouse_area, row, scrollable, text, text_input};
+use iced::{theme, Element, Length};
+
+use crate::data::search::Query;
+use crate::data::{Appointment, AppointmentKey, PDate};
+
+/// Finds appointments by their description, tags, priority and date
+#[derive(Debug, Clone, Default)]
+pub struct Search {
+    query: String,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Query(String),
+    EditAppointment(AppointmentKey, PDate),
+    ContextMenu(AppointmentKey, PDate),
+}
+
+impl Search {
+    pub fn update(&mut self, message: Message) {
+        match message {
+            Message::Query(query) => self.query = query,
+            // Opening the dialogs is done by the calendar
+            Message::EditAppointment(..) | Message::ContextMenu(..) => {}
+        }
+    }
+
+    pub fn view<'a>(&self, appointments: &[Appointment]) -> Element<'a, Message> {
+        let input = text_input(&t!("search-placeholder"), &self.query)
+            .on_input(Message::Query);
+        let results: Element<'a, Message> = match Query::parse(&self.query) {
+            Err(error) => text(error.to_string()).into(),
+            Ok(query) if query.is_empty() => text(t!("search-hint")).into(),
+            Ok(query) => {
+                let mut found: Vec<&Appointment> = appointments.iter()
+                    .filter(|appointment| query.matches(appointment))
+                    .collect();
+                found.sort_by_key(|appointment| (appointment.date.date(), appointment.sort_key()));
+                let mut list = column![text(t!("search-found", count = found.len()))].spacing(5);
+                for appointment in found {
+                    list = list.push(view_result(appointment));
+                }
+                scrollable(list).height(Length::Fill).into()
+            }
+        };
+        column![input, results]
+            .spacing(10)
+            .padding(5)
+            .into()
+    }
+}
+
+fn view_result<'a>(appointment: &Appointment) -> Element<'a, Message> {
+    let tags = appointment.tags.iter()
+        .flatten()
+        .filter(|tag| !tag.is_empty())
+        .cloned()
+        .collect::<Vec<String>>()
+        .join(", ");
+    let repeats = if appointment.recurrence.is_some() { format!(" {}", t!("search-repeats")) } else { String::new() };
+    mouse_area(
+        button(
+            row![
+                text(format!("{}{}", appointment.date.fmt(), repeats)).width(150),
+                text(appointment.time_string()).width(110),
+                text(appointment.description()).width(Length::Fill),
+                text(tags).size(12),
+                text(appointment.priority.to_string()).size(12),
+            ]
+            .spacing(10)
+        )
+        .width(Length::Fill)
+        .style(theme::Button::Secondary)
+        .on_press(Message::EditAppointment(appointment.key(), appointment.date))
+    )
+    .on_right_press(Message::ContextMenu(appointment.key(), appointment.date))
+    .into()
+}
@@ -0,0 +1,142 @@
+use std::path::PathBuf;
+
+use iced::widget::{button, column, row, text, text_input, PickList};
+use iced::{theme, Color, Element, Length, Theme};
+
+use crate::data::config::{self, Config, Language, StartView, ThemeChoice, WeekStart};
+use crate::data::date_format::DateFormat;
+use crate::data::file_path;
+use crate::data::theme::{self as palettes, Rgb};
+
+/// Edits the config, every change takes effect right away
+#[derive(Debug, Clone, Default)]
+pub struct Settings {
+    /// The data directory as typed, only used once confirmed
+    data_dir: String,
+    /// The built-in themes followed by the palettes of the user
+    themes: Vec<ThemeChoice>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Theme(ThemeChoice),
+    StartView(StartView),
+    DateFormat(DateFormat),
+    Language(Language),
+    FirstWeekday(WeekStart),
+    DataDir(String),
+    ApplyDataDir,
+    DefaultDataDir,
+    /// Replaces the config file that could not be read with the current settings
+    OverwriteConfig,
+}
+
+impl Settings {
+    pub fn new(config: &Config) -> Self {
+        let data_dir = config.data_dir.as_ref()
+            .map(|dir| dir.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let mut themes = ThemeChoice::BUILT_IN.to_vec();
+        // A broken theme file is reported when a palette from it is chosen
+        let names = palettes::load(&palettes::themes_file()).unwrap_or_default();
+        themes.extend(names.into_iter().map(|palette| ThemeChoice::Custom(palette.name)));
+        Settings { data_dir, themes }
+    }
+
+    pub fn update(&mut self, message: Message, config: &mut Config) {
+        match message {
+            Message::Theme(theme) => config.theme = theme,
+            Message::StartView(view) => config.start_view = view,
+            Message::DateFormat(format) => config.date_format = format,
+            Message::Language(language) => config.language = language,
+            Message::FirstWeekday(weekday) => config.first_weekday = weekday,
+            Message::DataDir(dir) => self.data_dir = dir,
+            Message::ApplyDataDir => {
+                let dir = self.data_dir.trim();
+                config.data_dir = if dir.is_empty() { None } else { Some(PathBuf::from(dir)) };
+            }
+            Message::DefaultDataDir => {
+                self.data_dir.clear();
+                config.data_dir = None;
+            }
+            // The application writes the file
+            Message::OverwriteConfig => {}
+        }
+    }
+
+    /// `error` is shown when the config could not be read or written, `unreadable` offers to replace the file
+    pub fn view<'a>(&self, config: &Config, error: Option<&str>, unreadable: bool) -> Element<'a, Message> {
+        let mut content = column![
+            text(t!("settings-title")).size(20),
+            setting(t!("settings-theme"), PickList::new(self.themes.clone(), Some(config.theme.clone()), Message::Theme).into()),
+            text(t!("settings-themes-hint", path = palettes::themes_file().display())).size(12),
+            setting(t!("settings-start-view"), PickList::new(StartView::ALL, Some(config.start_view), Message::StartView).into()),
+            setting(t!("settings-date-format"), PickList::new(DateFormat::ALL, Some(config.date_format), Message::DateFormat).into()),
+            setting(t!("settings-language"), PickList::new(Language::ALL, Some(config.language), Message::Language).into()),
+            setting(t!("settings-first-weekday"), PickList::new(WeekStart::ALL, Some(config.first_weekday), Message::FirstWeekday).into()),
+            setting(
+                t!("settings-data-dir"),
+                row![
+                    text_input(&file_path().to_string_lossy(), &self.data_dir)
+                        .on_input(Message::DataDir)
+                        .on_submit(Message::ApplyDataDir),
+                    button(text(t!("settings-apply")))
+                        .on_press(Message::ApplyDataDir),
+                    button(text(t!("settings-default")))
+                        .style(theme::Button::Secondary)
+                        .on_press(Message::DefaultDataDir),
+                ]
+                .spacing(5)
+                .into()
+            ),
+            text(t!("settings-data-dir-hint")).size(12),
+            text(t!("settings-saved-in", path = config::config_file().display())).size(12),
+        ]
+        .spacing(10)
+        .max_width(700);
+        if let Some(error) = error {
+            content = content.push(text(error));
+        }
+        if unreadable {
+            content = content.push(button(text(t!("settings-overwrite"))).on_press(Message::OverwriteConfig));
+        }
+        content.into()
+    }
+}
+
+/// The iced theme for `choice`, the error says why a palette of the user could not be used
+pub fn theme(choice: &ThemeChoice) -> Result<Theme, String> {
+    match choice {
+        ThemeChoice::Light => Ok(Theme::Light),
+        ThemeChoice::Dark => Ok(Theme::Dark),
+        ThemeChoice::System if palettes::system_is_dark() => Ok(Theme::Dark),
+        ThemeChoice::System => Ok(Theme::Light),
+        ThemeChoice::Custom(name) => {
+            let path = palettes::themes_file();
+            let palette = palettes::load(&path)
+                .map_err(|error| t!("settings-themes-failed", error = error))?
+                .into_iter()
+                .find(|palette| palette.name == *name)
+                .ok_or_else(|| t!("settings-theme-missing", name = name, path = path.display()))?;
+            Ok(Theme::custom(theme::Palette {
+                background: color(palette.background),
+                text: color(palette.text),
+                primary: color(palette.primary),
+                success: color(palette.success),
+                danger: color(palette.danger),
+            }))
+        }
+    }
+}
+
+fn color(Rgb(r, g, b): Rgb) -> Color {
+    Color::from_rgb8(r, g, b)
+}
+
+fn setting<'a>(label: String, input: Element<'a, Message>) -> Element<'a, Message> {
+    row![text(label).width(200), input]
+        .spacing(10)
+        .align_items(iced::Alignment::Center)
+        .width(Length::Fill)
+        .into()
+}
@@ -0,0 +1,194 @@
+use iced::advanced::layout::{self, Layout};
+use iced::advanced::overlay;
+use iced::advanced::renderer;
+use iced::advanced::widget::{self, Widget};
+use iced::advanced::{self, Clipboard, Shell};
+use iced::event;
+use iced::mouse;
+use iced::{Element, Event, Length, Rectangle};
+
+/// A widget that draws its layers on top of each other
+///
+/// The first layer decides the size, the others are laid out to fill it.
+/// Events reach the topmost layer first and stop at the first one capturing them.
+pub struct Stack<'a, Message, Renderer> {
+    layers: Vec<Element<'a, Message, Renderer>>,
+}
+
+impl<'a, Message, Renderer> Stack<'a, Message, Renderer> {
+    /// Returns a new [`Stack`] with `base` as its bottom layer
+    pub fn new(base: impl Into<Element<'a, Message, Renderer>>) -> Self {
+        Self {
+            layers: vec![base.into()],
+        }
+    }
+
+    /// Adds a layer on top of the existing ones
+    pub fn push(mut self, layer: impl Into<Element<'a, Message, Renderer>>) -> Self {
+        self.layers.push(layer.into());
+        self
+    }
+}
+
+impl<'a, Message, Renderer> Widget<Message, Renderer>
+    for Stack<'a, Message, Renderer>
+where
+    Renderer: advanced::Renderer,
+{
+    fn children(&self) -> Vec<widget::Tree> {
+        self.layers.iter().map(widget::Tree::new).collect()
+    }
+
+    fn diff(&self, tree: &mut widget::Tree) {
+        tree.diff_children(&self.layers);
+    }
+
+    fn width(&self) -> Length {
+        self.layers[0].as_widget().width()
+    }
+
+    fn height(&self) -> Length {
+        self.layers[0].as_widget().height()
+    }
+
+    fn layout(
+        &self,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        let base = self.layers[0].as_widget().layout(renderer, limits);
+        let size = base.size();
+        let layer_limits = layout::Limits::new(size, size);
+
+        let mut children = vec![base];
+        children.extend(
+            self.layers[1..]
+                .iter()
+                .map(|layer| layer.as_widget().layout(renderer, &layer_limits)),
+        );
+
+        layout::Node::with_children(size, children)
+    }
+
+    fn on_event(
+        &mut self,
+        state: &mut widget::Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) -> event::Status {
+        let layouts: Vec<Layout<'_>> = layout.children().collect();
+        for ((layer, state), layout) in self
+            .layers
+            .iter_mut()
+            .zip(&mut state.children)
+            .zip(layouts)
+            .rev()
+        {
+            let status = layer.as_widget_mut().on_event(
+                state,
+                event.clone(),
+                layout,
+                cursor,
+                renderer,
+                clipboard,
+                shell,
+                viewport,
+            );
+            if status == event::Status::Captured {
+                return status;
+            }
+        }
+        event::Status::Ignored
+    }
+
+    fn draw(
+        &self,
+        state: &widget::Tree,
+        renderer: &mut Renderer,
+        theme: &<Renderer as advanced::Renderer>::Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        for (i, ((layer, state), layout)) in self
+            .layers
+            .iter()
+            .zip(&state.children)
+            .zip(layout.children())
+            .enumerate()
+        {
+            if i == 0 {
+                layer.as_widget().draw(state, renderer, theme, style, layout, cursor, viewport);
+            } else {
+                // A new layer keeps the text of lower layers from being drawn above this one
+                renderer.with_layer(layout.bounds(), |renderer| {
+                    layer.as_widget().draw(state, renderer, theme, style, layout, cursor, viewport);
+                });
+            }
+        }
+    }
+
+    fn mouse_interaction(
+        &self,
+        state: &widget::Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        let layouts: Vec<Layout<'_>> = layout.children().collect();
+        self.layers
+            .iter()
+            .zip(&state.children)
+            .zip(layouts)
+            .rev()
+            .map(|((layer, state), layout)| {
+                layer.as_widget().mouse_interaction(state, layout, cursor, viewport, renderer)
+            })
+            .find(|interaction| *interaction != mouse::Interaction::default())
+            .unwrap_or_default()
+    }
+
+    fn operate(
+        &self,
+        state: &mut widget::Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn widget::Operation<Message>,
+    ) {
+        for ((layer, state), layout) in self
+            .layers
+            .iter()
+            .zip(&mut state.children)
+            .zip(layout.children())
+        {
+            layer.as_widget().operate(state, layout, renderer, operation);
+        }
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        state: &'b mut widget::Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+    ) -> Option<overlay::Element<'b, Message, Renderer>> {
+        overlay::from_children(&mut self.layers, state, layout, renderer)
+    }
+}
+
+impl<'a, Message, Renderer> From<Stack<'a, Message, Renderer>>
+    for Element<'a, Message, Renderer>
+where
+    Renderer: 'a + advanced::Renderer,
+    Message: 'a,
+{
+    fn from(stack: Stack<'a, Message, Renderer>) -> Self {
+        Element::new(stack)
+    }
+}
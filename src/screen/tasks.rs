@@ -0,0 +1,107 @@
+use iced::widget::{button, checkbox, column, mouse_area, row, scrollable, text, Space};
+use iced::{theme, Element, Length};
+
+use crate::data::{date, Task};
+use crate::data::task::Group;
+
+/// The tasks grouped by when they are due
+#[derive(Debug, Clone, Default)]
+pub struct Tasks {
+    /// Whether the done tasks are listed
+    show_done: bool,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    ToggleShowDone,
+    AddTask,
+    EditTask(i32),
+    ToggleTask(i32, bool),
+    ToggleChecklistItem(i32, usize, bool),
+}
+
+impl Tasks {
+    pub fn update(&mut self, message: Message) {
+        match message {
+            Message::ToggleShowDone => self.show_done = !self.show_done,
+            // Changing the tasks is done by the calendar
+            Message::AddTask | Message::EditTask(..) | Message::ToggleTask(..) | Message::ToggleChecklistItem(..) => {}
+        }
+    }
+
+    pub fn view<'a>(&self, tasks: &[Task]) -> Element<'a, Message> {
+        let today = date::now().date();
+        let mut sorted: Vec<&Task> = tasks.iter().collect();
+        sorted.sort_by_key(|task| task.sort_key());
+
+        let done_count = tasks.iter().filter(|task| task.is_done()).count();
+        let mut list = column![].spacing(15);
+        for group in Group::ALL.iter().filter(|group| **group != Group::Done || self.show_done) {
+            let in_group: Vec<&Task> = sorted.iter().copied().filter(|task| task.group(today) == *group).collect();
+            if in_group.is_empty() {
+                continue
+            }
+            let mut content = column![text(format!("{} ({})", group, in_group.len())).size(18)].spacing(5);
+            for task in in_group {
+                content = content.push(view_task(task));
+            }
+            list = list.push(content);
+        }
+        if tasks.iter().all(Task::is_done) {
+            list = list.push(text(t!("tasks-empty")));
+        }
+
+        column![
+            row![
+                text(t!("tasks-title")).size(20),
+                Space::with_width(Length::Fill),
+                button(text(if self.show_done { t!("tasks-hide-done", count = done_count) } else { t!("tasks-show-done", count = done_count) }))
+                    .style(theme::Button::Text)
+                    .on_press(Message::ToggleShowDone),
+                button(text(t!("tasks-new"))).on_press(Message::AddTask),
+            ]
+            .spacing(10),
+            scrollable(list).height(Length::Fill),
+        ]
+        .spacing(10)
+        .padding(5)
+        .into()
+    }
+}
+
+/// A task with its checklist, right click opens the task dialog
+fn view_task<'a>(task: &Task) -> Element<'a, Message> {
+    let id = task.id;
+    let when = match (task.completed, task.due) {
+        (Some(completed), _) => t!("task-done-on", date = completed.fmt()),
+        (None, Some(due)) => t!("task-due-on", date = due.fmt()),
+        (None, None) => "".to_string(),
+    };
+    let mut content = column![
+        row![
+            checkbox(task.label(), task.is_done(), move |done| Message::ToggleTask(id, done))
+                .width(Length::Fill),
+            text(when).size(14),
+            text(task.priority.to_string()).size(14).width(80),
+            button(text(t!("button-edit")))
+                .style(theme::Button::Secondary)
+                .on_press(Message::EditTask(id)),
+        ]
+        .spacing(10)
+        .align_items(iced::Alignment::Center)
+    ]
+    .spacing(5);
+    for (index, item) in task.checklist.iter().enumerate() {
+        content = content.push(
+            row![
+                Space::with_width(30),
+                checkbox(item.text.clone(), item.done, move |done| Message::ToggleChecklistItem(id, index, done))
+                    .size(14)
+                    .text_size(14),
+            ]
+        );
+    }
+    mouse_area(content)
+        .on_right_press(Message::EditTask(id))
+        .into()
+}